@@ -1,3 +1,5 @@
+use quizface::utils::lint::blessed_check;
+
 fn main() {
     // this function creates directories and logs
     // a 'masterhelp' txt file as well as logging blessed commands
@@ -25,33 +27,6 @@ fn main() {
     quizface::utils::logging::log_blessed_output(blessed);
 }
 
-fn blessed_check(raw_command_help: &str, command: &String) -> bool {
-    let delimiter_test_1: Vec<&str> =
-        raw_command_help.split("Result:\n").collect();
-    let delimiter_test_2: Vec<&str> =
-        raw_command_help.split("Examples:\n").collect();
-    if delimiter_test_1.len() == 2 && delimiter_test_2.len() != 1 {
-        let split_command_help =
-            raw_command_help.split("Result:\n").collect::<Vec<&str>>()[1]
-                .split("Examples:\n")
-                .collect::<Vec<&str>>()[0]
-                .trim();
-        if !split_command_help.is_empty()
-            && raw_command_help
-                .lines()
-                .next()
-                .unwrap()
-                .starts_with(command)
-        {
-            true
-        } else {
-            false
-        }
-    } else {
-        false
-    }
-}
-
 #[cfg(test)]
 mod unit {
     use super::*;
@@ -0,0 +1,52 @@
+//! Reports how the interpretations of two quizface runs differ, command by
+//! command, and whether each change breaks code reading the responses or
+//! sending the requests.
+//!
+//! usage: diff [--json] [--fail-on-breaking] <old interpretation dir>
+//!        <new interpretation dir>
+//!
+//! e.g. `diff output/v4.3.0_0.2.0 output/v4.4.0_0.2.0`
+//!
+//! Prints a Markdown report, or the changes as JSON with `--json`.  With
+//! `--fail-on-breaking` it exits non-zero if any change is breaking.
+use quizface::utils::diff::{
+    diff_commands, diff_markdown, diff_to_json, verdict, Compatibility,
+};
+use quizface::utils::model::load_commands;
+use std::path::Path;
+
+const USAGE: &str = "usage: diff [--json] [--fail-on-breaking] \
+                     <old interpretation dir> <new interpretation dir>";
+
+fn main() {
+    let mut json = false;
+    let mut fail_on_breaking = false;
+    let mut dirs = vec![];
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--fail-on-breaking" => fail_on_breaking = true,
+            _ => dirs.push(arg),
+        }
+    }
+    if dirs.len() != 2 {
+        panic!("{}", USAGE);
+    }
+    let diff = diff_commands(
+        &load_commands(Path::new(&dirs[0])),
+        &load_commands(Path::new(&dirs[1])),
+    );
+    if json {
+        println!(
+            "{}",
+            serde_json::ser::to_string_pretty(&diff_to_json(&diff))
+                .expect("Couldn't serialize prettily!")
+        );
+    } else {
+        print!("{}", diff_markdown(&diff, &dirs[0], &dirs[1]));
+    }
+    let (consumers, producers) = verdict(&diff);
+    if fail_on_breaking && consumers.max(producers) == Compatibility::Breaking {
+        std::process::exit(1);
+    }
+}
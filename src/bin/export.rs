@@ -0,0 +1,113 @@
+//! Exports the interpretations of one quizface run.
+//!
+//! usage: export <format> <interpretation dir> <destination dir>
+//!
+//! e.g. `export json-schema output/v4.3.0_0.2.0 schemas/v4.3.0`
+//!
+//! Formats: `json-schema`, `openrpc`, `rust-types`, `rust-client`,
+//! `typescript`, `python`, `protobuf`, `markdown`,
+//! `html` and `go`.
+use quizface::utils::export::{
+    go, json_schema, openrpc, protobuf, python, reference, rust_client,
+    rust_types, typescript, write_export,
+};
+use quizface::utils::logging::zcashd_version_of;
+use quizface::utils::model::load_commands;
+use std::path::Path;
+
+const USAGE: &str =
+    "usage: export <format> <interpretation dir> <destination dir>";
+
+fn main() {
+    let args = std::env::args().collect::<Vec<String>>();
+    if args.len() != 4 {
+        panic!("{}", USAGE);
+    }
+    let commands = load_commands(Path::new(&args[2]));
+    let destination = Path::new(&args[3]);
+    match args[1].as_str() {
+        "json-schema" => {
+            for command in &commands {
+                write_export(
+                    destination,
+                    &format!("{}.schema.json", command.name),
+                    &serde_json::ser::to_string_pretty(
+                        &json_schema::command_schema(command),
+                    )
+                    .expect("Couldn't serialize prettily!"),
+                );
+            }
+        }
+        "openrpc" => write_export(
+            destination,
+            "openrpc.json",
+            &serde_json::ser::to_string_pretty(&openrpc::openrpc_document(
+                &commands,
+                &zcashd_version_of(&args[2]),
+            ))
+            .expect("Couldn't serialize prettily!"),
+        ),
+        "rust-types" => write_export(
+            destination,
+            "responses.rs",
+            &rust_types::rust_module(&commands, &args[2]),
+        ),
+        // Goes next to `responses.rs`, which it uses.
+        "rust-client" => write_export(
+            destination,
+            "client.rs",
+            &rust_client::rust_client(&commands, &args[2]),
+        ),
+        "typescript" => write_export(
+            destination,
+            "zcashd.d.ts",
+            &typescript::typescript_declarations(&commands, &args[2]),
+        ),
+        format @ ("markdown" | "html") => {
+            let format = if format == "html" {
+                reference::Format::Html
+            } else {
+                reference::Format::Markdown
+            };
+            for command in &commands {
+                write_export(
+                    destination,
+                    &format!("{}.{}", command.name, format.extension()),
+                    &reference::command_page(command, format),
+                );
+            }
+            write_export(
+                destination,
+                &format!("index.{}", format.extension()),
+                &reference::index_page(
+                    &commands,
+                    &zcashd_version_of(&args[2]),
+                    format,
+                ),
+            );
+        }
+        "go" => {
+            for (file_name, contents) in go::go_files(&commands, &args[2]) {
+                write_export(destination, &file_name, &contents);
+            }
+        }
+        "protobuf" => write_export(
+            destination,
+            "zcashd.proto",
+            &protobuf::proto_file(&commands, &args[2]),
+        ),
+        "python" => {
+            write_export(
+                destination,
+                &format!("{}.py", python::TYPES_MODULE),
+                &python::python_types(&commands, &args[2]),
+            );
+            write_export(
+                destination,
+                "zcashd_client.py",
+                &python::python_client(&commands, &args[2]),
+            );
+        }
+        format => panic!("Unknown format '{}'. {}", format, USAGE),
+    }
+}
@@ -0,0 +1,43 @@
+//! Diffs the raw help two zcashd versions logged, command by command, and
+//! reports which changes the prescrub and scrub rules are affected by.
+//!
+//! usage: helpdiff [--json] <old log dir> <new log dir>
+//!
+//! e.g. `helpdiff logs/v4.3.0_0.2.0 logs/v4.4.0_0.2.0`
+//!
+//! Prints a Markdown report, or the differences as JSON with `--json`.
+use quizface::utils::help_diff::{
+    diff_help, help_diff_markdown, help_diff_to_json, load_raw_help,
+};
+use quizface::utils::logging::zcashd_version_of;
+use std::path::Path;
+
+const USAGE: &str = "usage: helpdiff [--json] <old log dir> <new log dir>";
+
+fn main() {
+    let mut args = std::env::args().skip(1).collect::<Vec<String>>();
+    let json = args.first().map(String::as_str) == Some("--json");
+    if json {
+        args.remove(0);
+    }
+    if args.len() != 2 {
+        panic!("{}", USAGE);
+    }
+    let (old_version, new_version) =
+        (zcashd_version_of(&args[0]), zcashd_version_of(&args[1]));
+    let diffs = diff_help(
+        &load_raw_help(Path::new(&args[0])),
+        &load_raw_help(Path::new(&args[1])),
+        &old_version,
+        &new_version,
+    );
+    if json {
+        println!(
+            "{}",
+            serde_json::ser::to_string_pretty(&help_diff_to_json(&diffs))
+                .expect("Couldn't serialize prettily!")
+        );
+    } else {
+        print!("{}", help_diff_markdown(&diffs, &old_version, &new_version));
+    }
+}
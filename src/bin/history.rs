@@ -0,0 +1,43 @@
+//! Reports when each command and field appeared, changed type or
+//! disappeared across the interpretations of several zcashd versions.
+//!
+//! usage: history [--json] <interpretation dir>...
+//!
+//! e.g. `history output/v4.2.0_0.2.0 output/v4.3.0_0.2.0 output/v4.4.0_0.2.0`
+//!
+//! The dirs go oldest first.  Prints a Markdown table per command, or the
+//! matrix as JSON with `--json`.
+use quizface::utils::history::History;
+use quizface::utils::logging::zcashd_version_of;
+use quizface::utils::model::load_commands;
+use std::path::Path;
+
+const USAGE: &str = "usage: history [--json] <interpretation dir>...";
+
+fn main() {
+    let mut json = false;
+    let mut runs = vec![];
+    for arg in std::env::args().skip(1) {
+        if arg == "--json" {
+            json = true;
+        } else {
+            runs.push((
+                zcashd_version_of(&arg),
+                load_commands(Path::new(&arg)),
+            ));
+        }
+    }
+    if runs.is_empty() {
+        panic!("{}", USAGE);
+    }
+    let history = History::new(&runs);
+    if json {
+        println!(
+            "{}",
+            serde_json::ser::to_string_pretty(&history.to_json())
+                .expect("Couldn't serialize prettily!")
+        );
+    } else {
+        print!("{}", history.to_markdown());
+    }
+}
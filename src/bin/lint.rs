@@ -0,0 +1,33 @@
+//! Lints raw help text against the conventions quizface's parser expects.
+//!
+//! usage: lint <command or raw help file>...
+//!
+//! An argument naming an existing file is linted as the raw help of the
+//! command its first word names, anything else is fetched from `zcash-cli`.
+//! Exits non-zero if there are any diagnostics.
+use quizface::utils::lint::lint_help;
+
+fn main() {
+    let mut diagnosed = false;
+    for arg in std::env::args().skip(1) {
+        let raw_command_help = if std::path::Path::new(&arg).is_file() {
+            std::fs::read_to_string(&arg).expect("Couldn't read raw help!")
+        } else {
+            let command_help_output = quizface::get_command_help(&arg);
+            String::from_utf8(command_help_output.stdout)
+                .expect("Invalid raw_command_help.")
+        };
+        let command = raw_command_help
+            .split_ascii_whitespace()
+            .next()
+            .unwrap_or(&arg)
+            .to_string();
+        for diagnostic in lint_help(&command, &raw_command_help) {
+            diagnosed = true;
+            println!("{}:{}: {}", arg, diagnostic.line, diagnostic.message);
+        }
+    }
+    if diagnosed {
+        std::process::exit(1);
+    }
+}
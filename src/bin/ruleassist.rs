@@ -0,0 +1,251 @@
+//! Shows every stage quizface takes a command's help through, to help write
+//! the prescrub and scrub rules (see `quizface::utils::rules`) it needs.
+//!
+//! usage: ruleassist <command> [--file <raw help>] [--zcashd-version <v>]
+//!        [--skeleton]
+//!
+//! Without `--file` the help is fetched from `zcash-cli`.  `--skeleton`
+//! prints a rule entry for the command to start editing from.
+use quizface::utils::prescrubbing::prescrub;
+use quizface::utils::rules::{parse_version, RuleOutcome};
+use quizface::utils::scrubbing::scrub;
+use quizface::{annotate_result, partition_help_text};
+use serde_json::{json, Value};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+// Sections of `partition_help_text`, in the order they occur.
+const SECTIONS: [&str; 4] =
+    ["description", "arguments", "response", "examples"];
+
+fn main() {
+    let args = std::env::args().collect::<Vec<String>>();
+    let option = |name: &str| {
+        args.iter()
+            .position(|arg| arg == name)
+            .map(|i| args.get(i + 1).expect("Option without a value!").clone())
+    };
+    let command = args
+        .get(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .expect("usage: ruleassist <command> [--file <raw help>] [--zcashd-version <v>] [--skeleton]");
+    let raw_command_help = match option("--file") {
+        Some(location) => std::fs::read_to_string(location)
+            .expect("Couldn't read raw help file!"),
+        None => {
+            let command_help_output = quizface::get_command_help(command);
+            String::from_utf8(command_help_output.stdout)
+                .expect("Invalid raw_command_help.")
+        }
+    };
+    let zcashd_version = option("--zcashd-version")
+        .unwrap_or_else(quizface::utils::logging::get_zcashd_version);
+
+    print_stage("raw", &raw_command_help);
+    let (prescrubbed, prescrub_outcomes) =
+        prescrub(command, &raw_command_help, &zcashd_version);
+    print_outcomes(&prescrub_outcomes);
+    print_stage("prescrubbed", &prescrubbed);
+
+    let sections = match caught(|| partition_help_text(&prescrubbed)) {
+        Ok(sections) => sections,
+        Err(payload) => {
+            println!(
+                "Couldn't partition the prescrubbed help: {}",
+                panic_message(payload)
+            );
+            skeleton(&args, command, "prescrub", &zcashd_version, None);
+            return;
+        }
+    };
+    for section in SECTIONS.iter() {
+        print_stage(&format!("section: {}", section), &sections[*section]);
+    }
+
+    let (scrubbed, scrub_outcomes) = scrub(
+        command.clone(),
+        sections["response"].clone(),
+        &zcashd_version,
+    );
+    print_outcomes(&scrub_outcomes);
+    print_stage("scrubbed", &scrubbed);
+
+    let mut failure = None;
+    for (index, result) in quizface::split_response_into_results(scrubbed)
+        .iter()
+        .enumerate()
+        .filter(|(_, result)| !result.is_empty())
+    {
+        match interpret_result(result) {
+            Ok(interpretation) => print_stage(
+                &format!("result {}", index),
+                &serde_json::ser::to_string_pretty(&interpretation)
+                    .expect("Couldn't serialize prettily!"),
+            ),
+            Err((line, message)) => {
+                let failing_line = result.lines().nth(line).unwrap_or("");
+                print_stage(
+                    &format!("result {}", index),
+                    &format!(
+                        "failed at line {} of the result: {}\n{}",
+                        line + 1,
+                        message,
+                        failing_line
+                    ),
+                );
+                failure.get_or_insert(failing_line.to_string());
+            }
+        }
+    }
+    if scrub_outcomes.iter().any(|o| o.default) {
+        println!(
+            "note: adding a rule for {} stops the `_` rules applying to it",
+            command
+        );
+    }
+    skeleton(&args, command, "scrub", &zcashd_version, failure.as_deref());
+}
+
+fn print_stage(name: &str, text: &str) {
+    println!("==== {} ====", name);
+    println!("{}", text);
+}
+
+fn print_outcomes(outcomes: &[RuleOutcome]) {
+    for outcome in outcomes {
+        println!(
+            "{} rule '{}': {}",
+            outcome.phase.name(),
+            outcome.rule_id,
+            if outcome.matched() {
+                "matched"
+            } else {
+                "did not match"
+            }
+        );
+    }
+}
+
+/// Interprets one scrubbed result variant, or returns the line of it the
+/// interpretation failed at, and why it failed.
+fn interpret_result(result: &str) -> Result<Value, (usize, String)> {
+    let mut result_chars = result.chars();
+    match caught(|| annotate_result(&mut result_chars)) {
+        Ok(interpretation) => Ok(interpretation),
+        Err(payload) => {
+            let message = panic_message(payload);
+            // Containers are only labelled once they are closed, so the
+            // line reached is where the failing container ends.
+            let consumed = result.len() - result_chars.as_str().len();
+            let reached = result[..consumed].matches('\n').count();
+            // `Label 'number' is invalid`: find the line quoting it instead.
+            let quoted = message.split('\'').nth(1).filter(|q| !q.is_empty());
+            let line = quoted
+                .and_then(|quoted| {
+                    result
+                        .lines()
+                        .take(reached + 1)
+                        .position(|line| line.contains(quoted))
+                })
+                .unwrap_or(reached);
+            Err((line, message))
+        }
+    }
+}
+
+// Runs `stage` without the default panic message, its failures being
+// reported with their location instead.  Panics elsewhere, e.g. in loading
+// a malformed rule file, still print theirs.
+fn caught<T>(stage: impl FnOnce() -> T) -> std::thread::Result<T> {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let result = catch_unwind(AssertUnwindSafe(stage));
+    std::panic::set_hook(hook);
+    result
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+fn skeleton(
+    args: &[String],
+    command: &str,
+    phase: &str,
+    zcashd_version: &str,
+    failing_line: Option<&str>,
+) {
+    if !args.iter().any(|arg| arg == "--skeleton") {
+        return;
+    }
+    let find = failing_line.unwrap_or("");
+    print_stage(
+        "skeleton rule",
+        &serde_json::ser::to_string_pretty(&skeleton_rule(
+            command,
+            phase,
+            zcashd_version,
+            find,
+        ))
+        .expect("Couldn't serialize prettily!"),
+    );
+}
+
+fn skeleton_rule(
+    command: &str,
+    phase: &str,
+    zcashd_version: &str,
+    find: &str,
+) -> Value {
+    json!({
+        "id": format!("{}/TODO", command),
+        "commands": [command],
+        "phase": phase,
+        "since": zcashd_version,
+        "until": next_release(zcashd_version),
+        "find": find,
+        "replace": find,
+    })
+}
+
+// The first release a rule written against `zcashd_version` hasn't been
+// checked against, e.g. `v4.4.0` for `v4.3.1`.
+fn next_release(zcashd_version: &str) -> String {
+    let version = parse_version(zcashd_version);
+    format!(
+        "v{}.{}.0",
+        version.first().unwrap_or(&0),
+        version.get(1).map_or(0, |minor| minor + 1)
+    )
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+
+    #[test]
+    fn interpret_result_locates_invalid_label() {
+        std::panic::set_hook(Box::new(|_| {}));
+        let failure = interpret_result(
+            "{\n  \"valid\": n,   (numeric) fine\n  \"invalid\": n,   (number) not fine\n}",
+        )
+        .unwrap_err();
+        let _ = std::panic::take_hook();
+        assert_eq!(failure, (2, "Label 'number' is invalid".to_string()));
+    }
+
+    #[test]
+    fn skeleton_rule_is_loadable() {
+        let rule = quizface::utils::rules::Rule::from_json(&skeleton_rule(
+            "getblock", "scrub", "v4.3.0", "(number)",
+        ));
+        assert!(rule.applies_to("getblock"));
+        assert!(rule.applies_to_version("v4.3.0"));
+        assert!(!rule.applies_to_version("v4.4.0"));
+    }
+}
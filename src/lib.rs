@@ -1,6 +1,12 @@
 pub mod utils;
 use crate::logging::create_log_dirs;
 use crate::logging::log_masterhelp_output;
+use crate::utils::arguments::structure_arguments;
+use crate::utils::capabilities::derive_capabilities;
+use crate::utils::description::structure_description;
+use crate::utils::examples::structure_examples;
+use crate::utils::fields::document_fields;
+use crate::utils::rules::{provenance, warn_stale, RuleOutcome};
 use crate::utils::scrubbing::scrub;
 use serde_json::{json, map::Map, Value};
 use std::collections::HashMap;
@@ -35,6 +41,26 @@ pub fn ingest_commands() -> Vec<String> {
     commands
 }
 
+pub fn ingest_categories() -> HashMap<String, String> {
+    let cli_help_output = get_command_help("");
+    let raw_help = std::string::String::from_utf8(cli_help_output.stdout)
+        .expect("Invalid, not UTF-8. Error!");
+    categorize_commands(&raw_help)
+}
+
+fn categorize_commands(raw_help: &str) -> HashMap<String, String> {
+    let mut categories = HashMap::new();
+    let mut category = String::new();
+    for line in raw_help.lines() {
+        if line.starts_with('=') {
+            category = line.trim_matches(|c| c == '=' || c == ' ').to_string();
+        } else if let Some(command) = line.split_ascii_whitespace().next() {
+            categories.insert(command.to_string(), category.clone());
+        }
+    }
+    categories
+}
+
 pub fn get_command_help(cmd_name: &str) -> std::process::Output {
     let command_help = std::process::Command::new(Path::new("zcash-cli"))
         .arg("help")
@@ -45,31 +71,104 @@ pub fn get_command_help(cmd_name: &str) -> std::process::Output {
 }
 
 fn record_interpretation(cmd_name: String, interpretation: String) {
+    record_output(format!("{}.json", cmd_name), interpretation);
+}
+
+fn record_metadata(cmd_name: String, metadata: String) {
+    record_output(format!("metadata/{}.json", cmd_name), metadata);
+}
+
+/// Records the `RunIndex` of the run next to its interpretations.
+pub fn record_index(index: String) {
+    record_output("index.json".to_string(), index);
+}
+
+fn record_output(relative_location: String, contents: String) {
     let rawlocation = &format!(
-        "./output/{}/{}.json",
+        "./output/{}/{}",
         utils::logging::create_version_name(),
-        cmd_name
+        relative_location
     );
     let location = std::path::Path::new(rawlocation);
     std::fs::create_dir_all(location.parent().unwrap()).unwrap();
     use std::io::Write as _;
     let mut file = std::fs::File::create(location)
         .expect("Couldn't create append interface to output file.");
-    file.write_all(interpretation.as_bytes()).unwrap();
+    file.write_all(contents.as_bytes()).unwrap();
 }
 
-pub fn produce_interpretation(raw_command_help: &str) {
-    let (cmd_name, interpretations) = interpret_help_message(raw_command_help);
+/// Records the interpretation and metadata of the prescrubbed
+/// `raw_command_help`, returning the outcomes of the scrub rules applied to
+/// it and the interpretation as recorded.  The metadata's provenance lists
+/// the corrections made by both `prescrub_outcomes` and the scrub rules.
+pub fn produce_interpretation(
+    raw_command_help: &str,
+    category: Option<&str>,
+    zcashd_version: &str,
+    prescrub_outcomes: &[RuleOutcome],
+    conditions: &[Option<String>],
+) -> (Vec<RuleOutcome>, String) {
+    let (cmd_name, interpretations, outcomes, scrubbed_response) =
+        interpret_help_message(raw_command_help, zcashd_version);
     let full_interp =
         &interpretations.iter().map(|x| x.clone()).collect::<Value>();
-    record_interpretation(
+    let interpretation = serde_json::ser::to_string_pretty(full_interp)
+        .expect("Couldn't serialize prettily!");
+    record_interpretation(cmd_name.clone(), interpretation.clone());
+    let sections = partition_help_text(raw_command_help);
+    logging::log_scrubbed_output(&cmd_name, &scrubbed_response);
+    let mut metadata =
+        document_help_message(&sections, &scrubbed_response, category);
+    if conditions.len() == interpretations.len()
+        && conditions.iter().any(Option::is_some)
+    {
+        metadata["result_conditions"] = json!(conditions);
+    }
+    metadata["provenance"] = provenance(
+        &prescrub_outcomes
+            .iter()
+            .chain(outcomes.iter())
+            .cloned()
+            .collect::<Vec<RuleOutcome>>(),
+    );
+    record_metadata(
         cmd_name,
-        serde_json::ser::to_string_pretty(full_interp)
+        serde_json::ser::to_string_pretty(&metadata)
             .expect("Couldn't serialize prettily!"),
     );
+    (outcomes, interpretation)
+}
+
+fn document_help_message(
+    sections: &HashMap<String, String>,
+    scrubbed_response: &str,
+    category: Option<&str>,
+) -> Value {
+    let cmd_name = sections.get("rpc_name").unwrap();
+    let description =
+        structure_description(sections.get("description").unwrap());
+    let capabilities = derive_capabilities(
+        cmd_name,
+        category,
+        &description,
+        sections.get("response").unwrap(),
+    );
+    let fields = split_response_into_results(scrubbed_response.to_string())
+        .iter()
+        .filter(|result| !result.is_empty())
+        .map(|result| Value::Object(document_fields(result)))
+        .collect::<Vec<Value>>();
+    json!({
+        "category": category,
+        "description": description,
+        "capabilities": capabilities,
+        "arguments": structure_arguments(sections.get("arguments").unwrap()),
+        "examples": structure_examples(sections.get("examples").unwrap()),
+        "fields": fields,
+    })
 }
 
-fn partition_help_text(raw_command_help: &str) -> HashMap<String, String> {
+pub fn partition_help_text(raw_command_help: &str) -> HashMap<String, String> {
     use regex::Regex;
     let mut sections = HashMap::new();
 
@@ -118,7 +217,38 @@ fn partition_help_text(raw_command_help: &str) -> HashMap<String, String> {
     sections
 }
 
-fn split_response_into_results(response_section: String) -> Vec<String> {
+/// What each `Result` header of `raw_command_help` documents its result
+/// for, e.g. `verbosity = 1` for `Result (for verbosity = 1):`, in help
+/// order.  Prescrubbing drops these, and patches may reorder the results,
+/// so there are none for help that was patched.
+pub fn result_conditions(
+    raw_command_help: &str,
+    prescrub_outcomes: &[RuleOutcome],
+) -> Vec<Option<String>> {
+    if prescrub_outcomes
+        .iter()
+        .any(|o| o.matched() && o.rule_id.contains(".patch#"))
+    {
+        return vec![];
+    }
+    let header = regex::Regex::new(r"(?m)^Result\b\s*(?:\((.*)\))?\s*:?\s*$")
+        .expect("invalid regex");
+    header
+        .captures_iter(raw_command_help)
+        .map(|caps| {
+            caps.get(1).map(|condition| {
+                let condition = condition.as_str().trim();
+                condition
+                    .strip_prefix("for ")
+                    .or_else(|| condition.strip_prefix("if "))
+                    .unwrap_or(condition)
+                    .to_string()
+            })
+        })
+        .collect()
+}
+
+pub fn split_response_into_results(response_section: String) -> Vec<String> {
     let resreg = regex::Regex::new(r"Result[:\s]").expect("invalid regex");
     let mut r: Vec<String> = resreg
         .split(&response_section)
@@ -127,29 +257,36 @@ fn split_response_into_results(response_section: String) -> Vec<String> {
     r.remove(0);
     r
 }
+/// The name, interpretation and scrub rule outcomes of the prescrubbed
+/// `raw_command_help`, and its response section as scrubbed.
 fn interpret_help_message(
     raw_command_help: &str,
-) -> (String, Vec<serde_json::Value>) {
+    zcashd_version: &str,
+) -> (String, Vec<serde_json::Value>, Vec<RuleOutcome>, String) {
     let sections = partition_help_text(raw_command_help);
     let cmd_name = sections.get("rpc_name").unwrap().to_string();
+    let response_data = sections.get("response").unwrap();
+    let (scrubbed_response, outcomes) =
+        scrub(cmd_name.clone(), response_data.clone(), zcashd_version);
+    warn_stale(&outcomes);
     if cmd_name == "submitblock" {
-        return (cmd_name, vec![json!("ENUM: duplicate, duplicate-invalid, duplicate-inconclusive, inconclusive, rejected")]);
+        return (cmd_name, vec![json!("ENUM: duplicate, duplicate-invalid, duplicate-inconclusive, inconclusive, rejected")], outcomes, scrubbed_response);
     }
-    let response_data = sections.get("response").unwrap();
-    let scrubbed_response = scrub(cmd_name.clone(), response_data.clone());
-    let results = split_response_into_results(scrubbed_response);
+    let results = split_response_into_results(scrubbed_response.clone());
     let mut v = vec![];
     if &results.len() == &1usize && &results[0] == "" {
-        (cmd_name, v)
+        (cmd_name, v, outcomes, scrubbed_response)
     } else {
         for result in results {
             v.push(annotate_result(&mut result.chars()));
         }
-        (cmd_name, v)
+        (cmd_name, v, outcomes, scrubbed_response)
     }
 }
 
-fn annotate_result(result_chars: &mut std::str::Chars) -> serde_json::Value {
+pub fn annotate_result(
+    result_chars: &mut std::str::Chars,
+) -> serde_json::Value {
     match result_chars.next().unwrap() {
         '{' => annotate_object(result_chars),
         '[' => annotate_array(result_chars),
@@ -371,6 +508,16 @@ mod unit {
         assert_eq!(result, expected_result);
     }
 
+    // ------------------ categorize_commands --------
+    #[test]
+    fn categorize_commands_masterhelp_fragment() {
+        let categories = categorize_commands(test::MASTERHELP_FRAGMENT);
+        assert_eq!(categories.len(), 6);
+        assert_eq!(categories.get("getblock").unwrap(), "Blockchain");
+        assert_eq!(categories.get("help").unwrap(), "Control");
+        assert_eq!(categories.get("z_exportkey").unwrap(), "Wallet");
+    }
+
     // ----------------scrub_result-------------------
     #[test]
     fn scrub_result_getblockchaininfo_scrubbed() {
@@ -378,7 +525,9 @@ mod unit {
         let result = scrub(
             "getblockchaininfo".to_string(),
             test::HELP_GETBLOCKCHAININFO_RESULT.to_string(),
-        );
+            test::ZCASHD_VERSION,
+        )
+        .0;
         assert_eq!(expected_result, result);
     }
 
@@ -647,7 +796,8 @@ mod unit {
     #[test]
     fn interpret_help_message_simple_unnested_full() {
         let simple_unnested_full = test::SIMPLE_UNNESTED_FULL;
-        let interpreted = interpret_help_message(simple_unnested_full);
+        let interpreted =
+            interpret_help_message(simple_unnested_full, test::ZCASHD_VERSION);
         let expected_result = json!({"outer_id":"String"});
         assert_eq!(interpreted.1[0], expected_result);
     }
@@ -656,101 +806,196 @@ mod unit {
     fn interpret_help_message_simple_nested_full() {
         use serde_json::json;
         let simple_nested_full = test::SIMPLE_NESTED_FULL;
-        let interpreted = interpret_help_message(simple_nested_full);
+        let interpreted =
+            interpret_help_message(simple_nested_full, test::ZCASHD_VERSION);
         let expected_result = json!({"outer_id":{"inner_id":"String"}});
         assert_eq!(interpreted.1[0], expected_result);
     }
 
+    // Patched commands have no scrub rules of their own, so are scrubbed
+    // with the `_` rules, which find nothing left to drop.
+    #[test]
+    fn interpret_help_message_patched_commands() {
+        use crate::utils::prescrubbing::prescrub;
+        for (command, raw_command_help) in [
+            ("getrawmempool", test::HELP_GETRAWMEMPOOL),
+            ("getblockheader", test::HELP_GETBLOCKHEADER),
+        ] {
+            let (prescrubbed, prescrub_outcomes) =
+                prescrub(command, raw_command_help, test::ZCASHD_VERSION);
+            assert!(prescrub_outcomes.iter().all(|o| !o.is_stale()));
+            let (_, interpretation, outcomes, _) =
+                interpret_help_message(&prescrubbed, test::ZCASHD_VERSION);
+            assert!(!outcomes.is_empty());
+            assert!(outcomes.iter().all(|o| o.default && !o.matched()));
+            let recorded = std::fs::read_to_string(format!(
+                "output/v4.3.0_0.2.0/{}.json",
+                command
+            ))
+            .unwrap();
+            assert_eq!(
+                Value::from(interpretation),
+                serde_json::from_str::<Value>(&recorded).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn interpret_help_message_strict_over_real_help() {
+        use crate::utils::prescrubbing::prescrub;
+        use crate::utils::rules::RuleReport;
+        // What `--strict` runs through, so a rule that can never match
+        // the help it was written for fails here first.
+        let mut rule_report = RuleReport::default();
+        for (command, raw_command_help) in [
+            ("getinfo", test::HELP_GETINFO),
+            ("getblockchaininfo", test::HELP_GETBLOCKCHAININFO_COMPLETE),
+            ("getrawmempool", test::HELP_GETRAWMEMPOOL),
+            ("getblockheader", test::HELP_GETBLOCKHEADER),
+            ("listtransactions", test::HELP_LISTTRANSACTIONS),
+        ] {
+            let (prescrubbed, prescrub_outcomes) =
+                prescrub(command, raw_command_help, test::ZCASHD_VERSION);
+            rule_report.record(prescrub_outcomes);
+            let (_, interpretation, outcomes, _) =
+                interpret_help_message(&prescrubbed, test::ZCASHD_VERSION);
+            rule_report.record(outcomes);
+            let recorded = std::fs::read_to_string(format!(
+                "output/v4.3.0_0.2.0/{}.json",
+                command
+            ))
+            .unwrap();
+            assert_eq!(
+                Value::from(interpretation),
+                serde_json::from_str::<Value>(&recorded).unwrap(),
+                "{}",
+                command
+            );
+        }
+        let stale = rule_report
+            .stale()
+            .iter()
+            .map(|o| o.rule_id.clone())
+            .collect::<Vec<String>>();
+        assert!(stale.is_empty(), "stale rules: {:?}", stale);
+    }
+
     #[test]
     #[should_panic]
     fn interpret_help_message_extrabrackets_within_input_lines() {
-        let valid_help_in =
-            interpret_help_message(test::EXTRABRACKETS3_HELP_GETINFO);
+        let valid_help_in = interpret_help_message(
+            test::EXTRABRACKETS3_HELP_GETINFO,
+            test::ZCASHD_VERSION,
+        );
         assert_eq!(valid_help_in.1[0], test::valid_getinfo_annotation());
     }
 
     #[test]
     #[should_panic]
     fn interpret_help_message_more_than_one_set_of_brackets_input() {
-        let valid_help_in =
-            interpret_help_message(test::MORE_BRACKET_PAIRS_HELP_GETINFO);
+        let valid_help_in = interpret_help_message(
+            test::MORE_BRACKET_PAIRS_HELP_GETINFO,
+            test::ZCASHD_VERSION,
+        );
         assert_eq!(valid_help_in.1[0], test::valid_getinfo_annotation());
     }
     #[test]
     #[should_panic]
     fn interpret_help_message_two_starting_brackets_input() {
-        let valid_help_in =
-            interpret_help_message(test::EXTRA_START_BRACKET_HELP_GETINFO);
+        let valid_help_in = interpret_help_message(
+            test::EXTRA_START_BRACKET_HELP_GETINFO,
+            test::ZCASHD_VERSION,
+        );
         assert_eq!(valid_help_in.1[0], test::valid_getinfo_annotation());
     }
     #[test]
     #[should_panic]
     fn interpret_help_message_two_ending_brackets_input() {
-        let valid_help_in =
-            interpret_help_message(test::EXTRA_END_BRACKET_HELP_GETINFO);
+        let valid_help_in = interpret_help_message(
+            test::EXTRA_END_BRACKET_HELP_GETINFO,
+            test::ZCASHD_VERSION,
+        );
         assert_eq!(valid_help_in.1[0], test::valid_getinfo_annotation());
     }
     #[test]
     #[should_panic]
     fn interpret_help_message_no_results_input() {
-        let valid_help_in =
-            interpret_help_message(test::NO_RESULT_HELP_GETINFO);
+        let valid_help_in = interpret_help_message(
+            test::NO_RESULT_HELP_GETINFO,
+            test::ZCASHD_VERSION,
+        );
         assert_eq!(valid_help_in.1[0], test::valid_getinfo_annotation());
     }
     #[test]
     #[should_panic]
     fn interpret_help_message_no_end_bracket_input() {
-        let valid_help_in =
-            interpret_help_message(test::NO_END_BRACKET_HELP_GETINFO);
+        let valid_help_in = interpret_help_message(
+            test::NO_END_BRACKET_HELP_GETINFO,
+            test::ZCASHD_VERSION,
+        );
         assert_eq!(valid_help_in.1[0], test::valid_getinfo_annotation());
     }
     #[test]
     #[should_panic]
     fn interpret_help_message_no_start_bracket_input() {
-        let valid_help_in =
-            interpret_help_message(test::NO_START_BRACKET_HELP_GETINFO);
+        let valid_help_in = interpret_help_message(
+            test::NO_START_BRACKET_HELP_GETINFO,
+            test::ZCASHD_VERSION,
+        );
         assert_eq!(valid_help_in.1[0], test::valid_getinfo_annotation());
     }
 
     #[ignore]
     #[test]
     fn interpret_help_message_upgrades_in_obj_extracted() {
-        dbg!(interpret_help_message(test::UPGRADES_IN_OBJ_EXTRACTED));
+        dbg!(interpret_help_message(
+            test::UPGRADES_IN_OBJ_EXTRACTED,
+            test::ZCASHD_VERSION
+        ));
     }
 
     // ----------------interpret_help_message---------------
 
     #[test]
     fn interpret_help_message_expected_input_valid() {
-        let valid_help_in = interpret_help_message(test::HELP_GETINFO);
+        let valid_help_in =
+            interpret_help_message(test::HELP_GETINFO, test::ZCASHD_VERSION);
         assert_eq!(valid_help_in.1[0], test::valid_getinfo_annotation());
     }
 
     #[test]
     fn interpret_help_message_early_lbracket_input() {
-        let valid_help_in =
-            interpret_help_message(test::LBRACKETY_HELP_GETINFO);
+        let valid_help_in = interpret_help_message(
+            test::LBRACKETY_HELP_GETINFO,
+            test::ZCASHD_VERSION,
+        );
         assert_eq!(valid_help_in.1[0], test::valid_getinfo_annotation());
     }
 
     #[test]
     fn interpret_help_message_early_rbracket_input() {
-        let valid_help_in =
-            interpret_help_message(test::RBRACKETY_HELP_GETINFO);
+        let valid_help_in = interpret_help_message(
+            test::RBRACKETY_HELP_GETINFO,
+            test::ZCASHD_VERSION,
+        );
         assert_eq!(valid_help_in.1[0], test::valid_getinfo_annotation());
     }
 
     #[test]
     fn interpret_help_message_early_extrabrackets_input() {
-        let valid_help_in =
-            interpret_help_message(test::EXTRABRACKETS1_HELP_GETINFO);
+        let valid_help_in = interpret_help_message(
+            test::EXTRABRACKETS1_HELP_GETINFO,
+            test::ZCASHD_VERSION,
+        );
         assert_eq!(valid_help_in.1[0], test::valid_getinfo_annotation());
     }
 
     #[test]
     fn interpret_help_message_late_extrabrackets_input() {
-        let valid_help_in =
-            interpret_help_message(test::EXTRABRACKETS2_HELP_GETINFO);
+        let valid_help_in = interpret_help_message(
+            test::EXTRABRACKETS2_HELP_GETINFO,
+            test::ZCASHD_VERSION,
+        );
         assert_eq!(valid_help_in.1[0], test::valid_getinfo_annotation());
     }
 
@@ -759,7 +1004,8 @@ mod unit {
         let expected_incoming = test::GETBLOCKCHAININFO_SOFTFORK_FRAGMENT;
         let expected_result = serde_json::json!({"softforks":[{"enforce":{"found":"Decimal","required":"Decimal","status":"bool","window":"Decimal"},"id":"String","reject":{"found":"Decimal","required":"Decimal","status":"bool","window":"Decimal"},"version":"Decimal"}]});
         assert_eq!(
-            interpret_help_message(expected_incoming).1[0],
+            interpret_help_message(expected_incoming, test::ZCASHD_VERSION).1
+                [0],
             expected_result
         );
     }
@@ -778,7 +1024,8 @@ mod unit {
                                                             "status":"bool",
                                                             "window":"Decimal"},
                                                   "version":"Decimal"});
-        let interpreted = interpret_help_message(expected_incoming);
+        let interpreted =
+            interpret_help_message(expected_incoming, test::ZCASHD_VERSION);
         assert_eq!(interpreted.1[0], expected_results);
     }
 
@@ -786,7 +1033,8 @@ mod unit {
     #[test]
     fn interpret_help_message_getblockchaininfo_complete_does_not_panic() {
         dbg!(interpret_help_message(
-            test::HELP_GETBLOCKCHAININFO_COMPLETE
+            test::HELP_GETBLOCKCHAININFO_COMPLETE,
+            test::ZCASHD_VERSION
         ));
     }
     fn getblockchaininfo_interpretation() -> serde_json::Value {
@@ -823,7 +1071,11 @@ mod unit {
         let expected = getblockchaininfo_interpretation();
         assert_eq!(
             expected,
-            interpret_help_message(test::HELP_GETBLOCKCHAININFO_COMPLETE).1[0]
+            interpret_help_message(
+                test::HELP_GETBLOCKCHAININFO_COMPLETE,
+                test::ZCASHD_VERSION
+            )
+            .1[0]
         );
     }
 
@@ -832,10 +1084,23 @@ mod unit {
     #[test]
     fn serde_json_value_help_getinfo() {
         let getinfo_serde_json_value = test::getinfo_export();
-        let help_getinfo = interpret_help_message(test::HELP_GETINFO);
+        let help_getinfo =
+            interpret_help_message(test::HELP_GETINFO, test::ZCASHD_VERSION);
         assert_eq!(getinfo_serde_json_value, help_getinfo.1[0]);
     }
 
+    #[test]
+    fn result_conditions_getblock() {
+        assert_eq!(
+            result_conditions(test::LINT_TWO_RESULTS, &[]),
+            vec![
+                Some("verbosity = 0".to_string()),
+                Some("verbosity = 1".to_string())
+            ]
+        );
+        assert_eq!(result_conditions(test::HELP_GETINFO, &[]), vec![None]);
+    }
+
     #[test]
     fn record_interpretation_getblockchaininfo() {
         //! This test simply shows that record_interpretation doesn't mutate-or
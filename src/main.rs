@@ -1,8 +1,25 @@
 use quizface::{
-    get_command_help, produce_interpretation,
-    utils::logging::{create_log_dirs, log_raw_output},
+    get_command_help, ingest_categories, produce_interpretation, record_index,
+    result_conditions,
+    utils::defects::{defect_report, defect_report_markdown},
+    utils::index::RunIndex,
+    utils::logging::{
+        create_log_dirs, get_zcashd_version, log_defect_report,
+        log_prescrubbed_output, log_raw_output, log_rule_report,
+        QUIZFACE_VERSION,
+    },
+    utils::rules::{warn_stale, RuleReport},
 };
-fn process_command(command: &str) {
+use std::collections::HashMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+fn process_command(
+    command: &str,
+    categories: &HashMap<String, String>,
+    zcashd_version: &str,
+    rule_report: &mut RuleReport,
+    index: &mut RunIndex,
+    strict: bool,
+) {
     let command_help_output = get_command_help(command);
     if !&command_help_output.status.success() {
         panic!()
@@ -13,14 +30,128 @@ fn process_command(command: &str) {
 
     log_raw_output(command, raw_command_help.to_string());
     //select just for blessed results.
-    let ps_command_help =
-        quizface::utils::prescrubbing::prescrub(command, raw_command_help);
-    produce_interpretation(&ps_command_help);
+    let (ps_command_help, prescrub_outcomes) =
+        quizface::utils::prescrubbing::prescrub(
+            command,
+            raw_command_help,
+            zcashd_version,
+        );
+    log_prescrubbed_output(command, &ps_command_help);
+    warn_stale(&prescrub_outcomes);
+    rule_report.record(prescrub_outcomes.clone());
+    if strict {
+        // fail before a stale prescrub rule can derail interpretation
+        exit_if_stale(rule_report, index, zcashd_version);
+    }
+    let category = categories.get(command).map(String::as_str);
+    let produced = catch_unwind(AssertUnwindSafe(|| {
+        produce_interpretation(
+            &ps_command_help,
+            category,
+            zcashd_version,
+            &prescrub_outcomes,
+            &result_conditions(raw_command_help, &prescrub_outcomes),
+        )
+    }));
+    match produced {
+        Ok((outcomes, interpretation)) => {
+            index.record(
+                command,
+                category,
+                raw_command_help,
+                Ok(&interpretation),
+                &[prescrub_outcomes, outcomes.clone()].concat(),
+            );
+            rule_report.record(outcomes);
+        }
+        Err(payload) => {
+            let failure = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "interpretation panicked".to_string());
+            index.record(
+                command,
+                category,
+                raw_command_help,
+                Err(&failure),
+                &prescrub_outcomes,
+            );
+            if strict {
+                fail(rule_report, index, zcashd_version);
+            }
+        }
+    }
+    if strict {
+        exit_if_stale(rule_report, index, zcashd_version);
+    }
+}
+
+fn write_index(index: &RunIndex, complete: bool) {
+    record_index(
+        serde_json::ser::to_string_pretty(&index.to_json(complete))
+            .expect("Couldn't serialize prettily!"),
+    );
+}
+
+fn report_rules(rule_report: &RuleReport, zcashd_version: &str) {
+    log_rule_report(
+        &serde_json::ser::to_string_pretty(&rule_report.to_json())
+            .expect("Couldn't serialize prettily!"),
+    );
+    let defects = defect_report(rule_report.outcomes(), zcashd_version);
+    log_defect_report(
+        &serde_json::ser::to_string_pretty(&defects)
+            .expect("Couldn't serialize prettily!"),
+        &defect_report_markdown(&defects),
+    );
+    let stale = rule_report.stale();
+    println!("Number of stale rules: {}", stale.len());
+    for outcome in stale {
+        println!("{} {}", outcome.command, outcome.rule_id);
+    }
+}
+
+fn exit_if_stale(
+    rule_report: &RuleReport,
+    index: &RunIndex,
+    zcashd_version: &str,
+) {
+    if !rule_report.stale().is_empty() {
+        fail(rule_report, index, zcashd_version);
+    }
 }
+
+// Ends a `--strict` run early, recording how far it got.
+fn fail(rule_report: &RuleReport, index: &RunIndex, zcashd_version: &str) -> ! {
+    write_index(index, false);
+    report_rules(rule_report, zcashd_version);
+    std::process::exit(1);
+}
+
 fn main() {
+    // usage: quizface [--strict] <command>...
+    // --strict exits with an error as soon as a rule fails to match.
     create_log_dirs();
-    for command in &std::env::args().collect::<Vec<String>>()[1..] {
-        process_command(&command);
+    let args = std::env::args().collect::<Vec<String>>();
+    let strict = args[1..].iter().any(|arg| arg == "--strict");
+    let categories = ingest_categories();
+    let zcashd_version = get_zcashd_version();
+    let mut rule_report = RuleReport::default();
+    let mut index = RunIndex::new(&zcashd_version, QUIZFACE_VERSION);
+    // replaced once the run is through, so an interrupted run is incomplete
+    write_index(&index, false);
+    for command in args[1..].iter().filter(|arg| !arg.starts_with("--")) {
+        process_command(
+            &command,
+            &categories,
+            &zcashd_version,
+            &mut rule_report,
+            &mut index,
+            strict,
+        );
     }
+    write_index(&index, true);
+    report_rules(&rule_report, &zcashd_version);
     dbg!("SUCCESS!");
 }
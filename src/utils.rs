@@ -1,4 +1,19 @@
+pub mod arguments;
+pub mod capabilities;
+pub mod defects;
+pub mod description;
+pub mod diff;
+pub mod examples;
+pub mod export;
+pub mod fields;
+pub mod help_diff;
+pub mod history;
+pub mod index;
+pub mod lint;
 pub mod logging;
+pub mod model;
+pub mod patching;
 pub mod prescrubbing;
+pub mod rules;
 pub mod scrubbing;
 pub mod test;
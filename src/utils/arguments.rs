@@ -0,0 +1,83 @@
+use regex::Regex;
+use serde_json::{json, Value};
+
+/// Turns the `"arguments"` section produced by `partition_help_text` into
+/// one `{"position", "name", "type", "required", "default", "description"}`
+/// per numbered argument.  `default` is only present when documented.
+///
+/// Description lines wrapped onto the following lines are joined; the
+/// nested JSON some arguments document their structure with is skipped.
+pub fn structure_arguments(arguments_section: &str) -> Value {
+    let argument_line =
+        Regex::new(r#"^\s*(\d+)\.\s+("[^"]*"|\S+)\s+\(([^)]*)\)\s*(.*)$"#)
+            .expect("Invalid regex!");
+    let default = Regex::new(r"default\s*=\s*(.*)").expect("Invalid regex!");
+    let mut arguments: Vec<Value> = vec![];
+    for line in arguments_section.lines() {
+        if let Some(caps) = argument_line.captures(line) {
+            let mut labels = caps[3].split(',').map(|label| label.trim());
+            let kind = labels.next().unwrap_or("");
+            let labels = labels.collect::<Vec<&str>>();
+            let mut argument = json!({
+                "position": caps[1].parse::<u64>().expect("Invalid position!"),
+                "name": caps[2].trim_matches('"'),
+                "type": kind,
+                "required": !labels.contains(&"optional"),
+                "description": caps[4].trim(),
+            });
+            if let Some(default) =
+                labels.iter().find_map(|label| default.captures(label))
+            {
+                argument["default"] = json!(default[1].trim_matches('"'));
+            }
+            arguments.push(argument);
+        } else if let Some(last) = arguments.last_mut() {
+            let continued = line.trim();
+            if !continued.is_empty()
+                && !continued.starts_with(['{', '}', '[', ']', '"', ','])
+            {
+                let description = format!(
+                    "{} {}",
+                    last["description"].as_str().unwrap_or(""),
+                    continued
+                );
+                last["description"] = json!(description.trim());
+            }
+        }
+    }
+    Value::Array(arguments)
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use crate::utils::test;
+
+    #[test]
+    fn structure_arguments_z_sendmany() {
+        let arguments = structure_arguments(test::Z_SENDMANY_ARGUMENTS);
+        assert_eq!(arguments.as_array().unwrap().len(), 4);
+        assert_eq!(
+            arguments[0],
+            json!({
+                "position": 1,
+                "name": "fromaddress",
+                "type": "string",
+                "required": true,
+                "description": "The taddr or zaddr to send the funds from."
+            })
+        );
+        assert_eq!(arguments[1]["type"], "array");
+        assert_eq!(
+            arguments[3],
+            json!({
+                "position": 4,
+                "name": "fee",
+                "type": "numeric",
+                "required": false,
+                "default": "0.0001",
+                "description": "The fee amount to attach to this transaction."
+            })
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use regex::Regex;
+use serde_json::{json, Value};
+
+// Masterhelp categories whose RPCs are only registered with a wallet.
+const WALLET_CATEGORIES: [&str; 2] = ["Wallet", "Disclosure"];
+
+// Name prefixes of RPCs that change node, wallet or mempool state.
+const MUTATING_PREFIXES: [&str; 23] = [
+    "add",
+    "clear",
+    "disconnect",
+    "encrypt",
+    "generate",
+    "import",
+    "invalidate",
+    "keypoolrefill",
+    "lockunspent",
+    "move",
+    "prioritise",
+    "reconsider",
+    "send",
+    "set",
+    "stop",
+    "submit",
+    "walletlock",
+    "walletpassphrase",
+    "z_import",
+    "z_merge",
+    "z_send",
+    "z_set",
+    "z_shield",
+];
+
+// RPCs that allocate a fresh key from the keypool.
+const KEY_ALLOCATING: [&str; 4] = [
+    "getaccountaddress",
+    "getnewaddress",
+    "getrawchangeaddress",
+    "z_getnewaddress",
+];
+
+/// Derives the machine-readable capability flags of a command from its
+/// masterhelp category, its structured description (see
+/// `structure_description`) and its raw response section.
+pub fn derive_capabilities(
+    cmd_name: &str,
+    category: Option<&str>,
+    description: &Value,
+    response_section: &str,
+) -> Value {
+    let text = description_text(description);
+    let warning_kinds = description["warnings"]
+        .as_array()
+        .map(|warnings| {
+            warnings
+                .iter()
+                .filter_map(|w| w["kind"].as_str())
+                .collect::<Vec<&str>>()
+        })
+        .unwrap_or_default();
+
+    let returns_secret =
+        Regex::new(r"(?im)^(reveals|dumps|exports)\b.*keys?\b")
+            .expect("Invalid regex!")
+            .is_match(&text)
+            || response_section.contains("secretkey")
+            || response_section.contains("spendingkey");
+    let requires_unlocked_wallet = text.contains("wallet passphrase")
+        || (returns_secret && category == Some("Wallet"));
+    let requires_wallet = requires_unlocked_wallet
+        || category.is_some_and(|c| WALLET_CATEGORIES.contains(&c));
+    let experimental = warning_kinds.contains(&"experimental")
+        || text.contains("-experimentalfeatures");
+    let deprecated =
+        warning_kinds.contains(&"deprecated") || text.contains("DEPRECATED");
+    let mutates_state = KEY_ALLOCATING.contains(&cmd_name)
+        || MUTATING_PREFIXES.iter().any(|p| cmd_name.starts_with(p));
+
+    json!({
+        "requires_wallet": requires_wallet,
+        "requires_unlocked_wallet": requires_unlocked_wallet,
+        "experimental": experimental,
+        "deprecated": deprecated,
+        "mutates_state": mutates_state,
+        "returns_secret": returns_secret,
+    })
+}
+
+fn description_text(description: &Value) -> String {
+    let mut text = vec![description["summary"].as_str().unwrap_or("")];
+    for key in &["body", "notes"] {
+        if let Some(paragraphs) = description[key].as_array() {
+            text.extend(paragraphs.iter().filter_map(|p| p.as_str()));
+        }
+    }
+    text.join("\n")
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use crate::utils::description::structure_description;
+    use crate::utils::test;
+
+    #[test]
+    fn derive_capabilities_dumpprivkey() {
+        let description = structure_description(test::DUMPPRIVKEY_DESCRIPTION);
+        let capabilities = derive_capabilities(
+            "dumpprivkey",
+            Some("Wallet"),
+            &description,
+            r#""key"                (string) The private key"#,
+        );
+        assert_eq!(capabilities["returns_secret"], true);
+        assert_eq!(capabilities["requires_wallet"], true);
+        assert_eq!(capabilities["requires_unlocked_wallet"], true);
+        assert_eq!(capabilities["mutates_state"], false);
+        assert_eq!(capabilities["deprecated"], false);
+    }
+
+    #[test]
+    fn derive_capabilities_deprecated_and_experimental() {
+        let description =
+            structure_description(test::DEPRECATED_EXPERIMENTAL_DESCRIPTION);
+        let capabilities =
+            derive_capabilities("getaccount", Some("Wallet"), &description, "");
+        assert_eq!(capabilities["experimental"], true);
+        assert_eq!(capabilities["deprecated"], true);
+        assert_eq!(capabilities["requires_unlocked_wallet"], true);
+        assert_eq!(capabilities["returns_secret"], false);
+    }
+
+    #[test]
+    fn derive_capabilities_blockchain() {
+        let description = structure_description(
+            test::HELP_GETBLOCKCHAININFO_COMPLETE
+                .split("Result:")
+                .next()
+                .unwrap(),
+        );
+        let capabilities = derive_capabilities(
+            "getblockchaininfo",
+            Some("Blockchain"),
+            &description,
+            test::HELP_GETBLOCKCHAININFO_RESULT,
+        );
+        for flag in capabilities.as_object().unwrap().values() {
+            assert_eq!(flag, false);
+        }
+    }
+}
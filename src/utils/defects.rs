@@ -0,0 +1,170 @@
+use crate::utils::rules::RuleOutcome;
+use serde_json::{json, map::Map, Value};
+
+// Fragments of the name a rule id gives after its command, checked in
+// order, and the zcashd help-text defect the rule corrects.
+const DEFECT_KINDS: [(&str, &str); 10] = [
+    (".patch#", "restructured result"),
+    ("ellipsis", "ellipsis in place of repeated fields"),
+    ("-to-numeric", "non-standard type word"),
+    ("-to-boolean", "non-standard type word"),
+    ("-to-hexadecimal", "non-standard type word"),
+    ("result", "missing or non-standard Result: header"),
+    ("examples", "missing or misplaced Examples: header"),
+    ("colon", "missing colon"),
+    ("label", "non-standard type label"),
+    ("array", "array documented without brackets"),
+];
+
+fn defect_kind(rule_id: &str) -> &'static str {
+    // `getblock/result-header` -> `result-header`
+    let name = rule_id.split_once('/').map_or(rule_id, |(_, name)| name);
+    DEFECT_KINDS
+        .iter()
+        .find(|(fragment, _)| name.contains(fragment))
+        .map_or("malformed field", |(_, kind)| kind)
+}
+
+/// Reports every correction `outcomes` made as a defect in the help text of
+/// `zcashd_version`, with the raw snippet and its corrected form.  Rules
+/// written for a command that no longer match are listed as possibly fixed.
+pub fn defect_report(outcomes: &[RuleOutcome], zcashd_version: &str) -> Value {
+    let mut commands = Map::new();
+    for outcome in outcomes {
+        if let Some(change) = &outcome.change {
+            let defects = commands
+                .entry(outcome.command.clone())
+                .or_insert_with(|| json!([]));
+            defects.as_array_mut().unwrap().push(json!({
+                "rule_id": outcome.rule_id,
+                "phase": outcome.phase.name(),
+                "kind": defect_kind(&outcome.rule_id),
+                "raw": change.before,
+                "corrected": change.after,
+            }));
+        }
+    }
+    let fixed = outcomes
+        .iter()
+        .filter(|o| o.is_stale())
+        .map(|o| {
+            json!({
+                "rule_id": o.rule_id,
+                "command": o.command,
+                "phase": o.phase.name(),
+            })
+        })
+        .collect::<Vec<Value>>();
+    json!({
+        "zcashd_version": zcashd_version,
+        "commands": commands,
+        "possibly_fixed": fixed,
+    })
+}
+
+/// Renders a `defect_report` as Markdown, for filing upstream.
+pub fn defect_report_markdown(report: &Value) -> String {
+    let mut markdown = format!(
+        "# zcashd help text defects ({})\n",
+        report["zcashd_version"].as_str().unwrap_or("")
+    );
+    if let Some(commands) = report["commands"].as_object() {
+        for (command, defects) in commands {
+            markdown.push_str(&format!("\n## `{}`\n", command));
+            for defect in defects.as_array().unwrap() {
+                markdown.push_str(&format!(
+                    "\n- {} (`{}`)\n\n  Raw:\n\n{}\n  Corrected:\n\n{}",
+                    defect["kind"].as_str().unwrap(),
+                    defect["rule_id"].as_str().unwrap(),
+                    fence(defect["raw"].as_str().unwrap()),
+                    fence(defect["corrected"].as_str().unwrap()),
+                ));
+            }
+        }
+    }
+    if let Some(fixed) = report["possibly_fixed"].as_array() {
+        if !fixed.is_empty() {
+            markdown.push_str(
+                "\n## Possibly fixed\n\nThese rules no longer match.\n\n\
+                 | command | rule |\n|---|---|\n",
+            );
+            for outcome in fixed {
+                markdown.push_str(&format!(
+                    "| `{}` | `{}` |\n",
+                    outcome["command"].as_str().unwrap(),
+                    outcome["rule_id"].as_str().unwrap(),
+                ));
+            }
+        }
+    }
+    markdown
+}
+
+fn fence(snippet: &str) -> String {
+    let mut fenced = "  ```\n".to_string();
+    for line in snippet.lines() {
+        fenced.push_str(&format!("  {}\n", line));
+    }
+    fenced.push_str("  ```\n");
+    fenced
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use crate::utils::rules::{Change, Phase};
+
+    #[test]
+    fn defect_report_lists_corrections_and_stale_rules() {
+        let outcome = |rule_id: &str, change| RuleOutcome {
+            rule_id: rule_id.to_string(),
+            command: "getaddressdeltas".to_string(),
+            phase: Phase::Scrub,
+            default: false,
+            change,
+        };
+        let report = defect_report(
+            &[
+                outcome(
+                    "getaddressdeltas/number-to-numeric",
+                    Some(Change::new("(number)", "(numeric)")),
+                ),
+                outcome("getaddressdeltas/drop-ellipsis", None),
+            ],
+            "v4.3.0",
+        );
+        assert_eq!(
+            report["commands"]["getaddressdeltas"],
+            json!([{
+                "rule_id": "getaddressdeltas/number-to-numeric",
+                "phase": "scrub",
+                "kind": "non-standard type word",
+                "raw": "(number)",
+                "corrected": "(numeric)",
+            }])
+        );
+        assert_eq!(
+            report["possibly_fixed"][0]["rule_id"],
+            "getaddressdeltas/drop-ellipsis"
+        );
+        let markdown = defect_report_markdown(&report);
+        assert!(markdown.contains("## `getaddressdeltas`"));
+        assert!(markdown.contains("  ```\n  (numeric)\n  ```\n"));
+    }
+
+    #[test]
+    fn defect_kind_ignores_command_name() {
+        assert_eq!(
+            defect_kind("z_getoperationresult/insufficient-information"),
+            "malformed field"
+        );
+        assert_eq!(
+            defect_kind("listaddressgroupings/array-brackets"),
+            "array documented without brackets"
+        );
+        assert_eq!(
+            defect_kind("v4.3.0/getrawmempool.patch#1"),
+            "restructured result"
+        );
+    }
+}
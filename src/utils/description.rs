@@ -0,0 +1,126 @@
+use serde_json::{json, Value};
+
+// Leading markers zcashd uses to flag an RPC, mapped to the warning kind
+// they are recorded as.
+const WARNING_MARKERS: [(&str, &str); 4] = [
+    ("DEPRECATED", "deprecated"),
+    ("EXPERIMENTAL FEATURE", "experimental"),
+    ("EXPERIMENTAL", "experimental"),
+    ("WARNING", "warning"),
+];
+
+const NOTE_MARKERS: [&str; 4] =
+    ["Note:", "NOTE:", "Note that", "Requires wallet passphrase"];
+
+/// Turns the `"description"` section produced by `partition_help_text`
+/// into `{"usage", "summary", "body", "warnings", "notes"}`.
+pub fn structure_description(description_section: &str) -> Value {
+    let mut lines = description_section
+        .lines()
+        .map(|line| line.trim())
+        .skip_while(|line| line.is_empty());
+    let usage = lines.next().unwrap_or("").to_string();
+
+    let mut paragraphs: Vec<String> = vec![];
+    let mut current: Vec<&str> = vec![];
+    for line in lines {
+        if line.is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(current.join("\n"));
+                current.clear();
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current.join("\n"));
+    }
+
+    let mut body = vec![];
+    let mut warnings = vec![];
+    let mut notes = vec![];
+    for paragraph in paragraphs {
+        if let Some((kind, remainder)) = split_warning(&paragraph) {
+            warnings.push(json!({"kind": kind, "text": paragraph}));
+            // `DEPRECATED. Returns the account...` still documents the RPC.
+            if kind == "deprecated" && !remainder.is_empty() {
+                body.push(remainder);
+            }
+        } else if NOTE_MARKERS.iter().any(|m| paragraph.starts_with(m)) {
+            notes.push(paragraph);
+        } else {
+            body.push(paragraph);
+        }
+    }
+    let summary = if body.is_empty() {
+        String::new()
+    } else {
+        body.remove(0).replace('\n', " ")
+    };
+    json!({
+        "usage": usage,
+        "summary": summary,
+        "body": body,
+        "warnings": warnings,
+        "notes": notes,
+    })
+}
+
+fn split_warning(paragraph: &str) -> Option<(&'static str, String)> {
+    for (marker, kind) in WARNING_MARKERS.iter() {
+        if let Some(rest) = paragraph.strip_prefix(marker) {
+            if rest.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+                continue;
+            }
+            let remainder = rest.trim_start_matches(['.', ':', ';']).trim();
+            return Some((kind, remainder.to_string()));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use crate::utils::test;
+
+    #[test]
+    fn structure_description_getblockchaininfo() {
+        let description = structure_description(
+            test::HELP_GETBLOCKCHAININFO_COMPLETE
+                .split("Result:")
+                .next()
+                .unwrap(),
+        );
+        assert_eq!(description["usage"], "getblockchaininfo");
+        assert_eq!(
+            description["summary"],
+            "Returns an object containing various state info regarding block chain processing."
+        );
+        assert_eq!(description["notes"].as_array().unwrap().len(), 1);
+        assert!(description["warnings"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn structure_description_deprecated_and_experimental() {
+        let description =
+            structure_description(test::DEPRECATED_EXPERIMENTAL_DESCRIPTION);
+        assert_eq!(description["usage"], "getaccount \"zcashaddress\"");
+        assert_eq!(
+            description["summary"],
+            "Returns the account associated with the given address."
+        );
+        let kinds = description["warnings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|w| w["kind"].as_str().unwrap())
+            .collect::<Vec<&str>>();
+        assert_eq!(kinds, vec!["experimental", "deprecated"]);
+        assert_eq!(
+            description["notes"][0],
+            "Requires wallet passphrase to be set with walletpassphrase call."
+        );
+    }
+}
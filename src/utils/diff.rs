@@ -0,0 +1,807 @@
+//! Structural differences between the interpretations of two zcashd
+//! versions, e.g. `output/v4.3.0_0.2.0` and `output/v4.4.0_0.2.0`.
+use crate::utils::model::{Argument, Command, Shape};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// How a change affects code written against the older version, from
+/// least to most.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Compatibility {
+    Unaffected,
+    /// Something new that existing code can ignore.
+    Additive,
+    /// Existing code may stop working.
+    Breaking,
+}
+
+impl Compatibility {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Compatibility::Unaffected => "unaffected",
+            Compatibility::Additive => "additive",
+            Compatibility::Breaking => "breaking",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchemaChange {
+    CommandAdded,
+    CommandRemoved,
+    VariantAdded {
+        variant: usize,
+    },
+    VariantRemoved {
+        variant: usize,
+    },
+    /// `path` is that of the field in its result variant, e.g.
+    /// `tx[].vin[].txid`.
+    FieldAdded {
+        variant: usize,
+        path: String,
+        optional: bool,
+    },
+    FieldRemoved {
+        variant: usize,
+        path: String,
+        optional: bool,
+    },
+    TypeChanged {
+        variant: usize,
+        path: String,
+        old: String,
+        new: String,
+    },
+    OptionalityChanged {
+        variant: usize,
+        path: String,
+        optional: bool,
+    },
+    ArgumentAdded {
+        position: usize,
+        name: String,
+        required: bool,
+    },
+    ArgumentRemoved {
+        position: usize,
+        name: String,
+    },
+    ArgumentRenamed {
+        position: usize,
+        old: String,
+        new: String,
+    },
+    ArgumentTypeChanged {
+        position: usize,
+        name: String,
+        old: String,
+        new: String,
+    },
+    ArgumentRequirednessChanged {
+        position: usize,
+        name: String,
+        required: bool,
+    },
+}
+
+impl SchemaChange {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SchemaChange::CommandAdded => "command_added",
+            SchemaChange::CommandRemoved => "command_removed",
+            SchemaChange::VariantAdded { .. } => "variant_added",
+            SchemaChange::VariantRemoved { .. } => "variant_removed",
+            SchemaChange::FieldAdded { .. } => "field_added",
+            SchemaChange::FieldRemoved { .. } => "field_removed",
+            SchemaChange::TypeChanged { .. } => "type_changed",
+            SchemaChange::OptionalityChanged { .. } => "optionality_changed",
+            SchemaChange::ArgumentAdded { .. } => "argument_added",
+            SchemaChange::ArgumentRemoved { .. } => "argument_removed",
+            SchemaChange::ArgumentRenamed { .. } => "argument_renamed",
+            SchemaChange::ArgumentTypeChanged { .. } => "argument_type_changed",
+            SchemaChange::ArgumentRequirednessChanged { .. } => {
+                "argument_requiredness_changed"
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut change = match self {
+            SchemaChange::CommandAdded | SchemaChange::CommandRemoved => {
+                json!({})
+            }
+            SchemaChange::VariantAdded { variant }
+            | SchemaChange::VariantRemoved { variant } => {
+                json!({ "variant": variant })
+            }
+            SchemaChange::FieldAdded {
+                variant,
+                path,
+                optional,
+            } => {
+                json!({"variant": variant, "path": path, "optional": optional})
+            }
+            SchemaChange::FieldRemoved {
+                variant,
+                path,
+                optional,
+            } => {
+                json!({"variant": variant, "path": path, "optional": optional})
+            }
+            SchemaChange::TypeChanged {
+                variant,
+                path,
+                old,
+                new,
+            } => {
+                json!({"variant": variant, "path": path, "old": old, "new": new})
+            }
+            SchemaChange::OptionalityChanged {
+                variant,
+                path,
+                optional,
+            } => {
+                json!({"variant": variant, "path": path, "optional": optional})
+            }
+            SchemaChange::ArgumentAdded {
+                position,
+                name,
+                required,
+            } => {
+                json!({"position": position, "name": name, "required": required})
+            }
+            SchemaChange::ArgumentRemoved { position, name } => {
+                json!({"position": position, "name": name})
+            }
+            SchemaChange::ArgumentRenamed { position, old, new } => {
+                json!({"position": position, "old": old, "new": new})
+            }
+            SchemaChange::ArgumentTypeChanged {
+                position,
+                name,
+                old,
+                new,
+            } => {
+                json!({"position": position, "name": name, "old": old, "new": new})
+            }
+            SchemaChange::ArgumentRequirednessChanged {
+                position,
+                name,
+                required,
+            } => {
+                json!({"position": position, "name": name, "required": required})
+            }
+        };
+        change["kind"] = json!(self.kind());
+        change["consumers"] = json!(self.for_consumers().name());
+        change["producers"] = json!(self.for_producers().name());
+        change
+    }
+
+    /// What the change means to code reading the responses of the command.
+    pub fn for_consumers(&self) -> Compatibility {
+        match self {
+            SchemaChange::CommandRemoved => Compatibility::Breaking,
+            // A shape the consumer doesn't decode yet.
+            SchemaChange::VariantAdded { .. } => Compatibility::Breaking,
+            SchemaChange::FieldRemoved { optional, .. } => {
+                if *optional {
+                    // Consumers already handle its absence.
+                    Compatibility::Unaffected
+                } else {
+                    Compatibility::Breaking
+                }
+            }
+            SchemaChange::TypeChanged { .. } => Compatibility::Breaking,
+            SchemaChange::OptionalityChanged { optional, .. } => {
+                if *optional {
+                    Compatibility::Breaking
+                } else {
+                    Compatibility::Unaffected
+                }
+            }
+            SchemaChange::CommandAdded | SchemaChange::FieldAdded { .. } => {
+                Compatibility::Additive
+            }
+            _ => Compatibility::Unaffected,
+        }
+    }
+
+    /// What the change means to code sending requests for the command.
+    /// zcashd takes params by position, so renaming an argument doesn't
+    /// matter to them.
+    pub fn for_producers(&self) -> Compatibility {
+        match self {
+            SchemaChange::CommandAdded => Compatibility::Additive,
+            SchemaChange::CommandRemoved => Compatibility::Breaking,
+            // The params that selected it get another result.
+            SchemaChange::VariantRemoved { .. } => Compatibility::Breaking,
+            SchemaChange::ArgumentAdded { required, .. }
+            | SchemaChange::ArgumentRequirednessChanged { required, .. } => {
+                if *required {
+                    Compatibility::Breaking
+                } else {
+                    Compatibility::Additive
+                }
+            }
+            SchemaChange::ArgumentRemoved { .. }
+            | SchemaChange::ArgumentTypeChanged { .. } => {
+                Compatibility::Breaking
+            }
+            _ => Compatibility::Unaffected,
+        }
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        self.for_consumers() == Compatibility::Breaking
+            || self.for_producers() == Compatibility::Breaking
+    }
+
+    /// One line describing the change, e.g. ``field `size` of result 1
+    /// removed``.
+    pub fn describe(&self) -> String {
+        match self {
+            SchemaChange::CommandAdded => "command added".to_string(),
+            SchemaChange::CommandRemoved => "command removed".to_string(),
+            SchemaChange::VariantAdded { variant } => {
+                format!("result {} added", variant)
+            }
+            SchemaChange::VariantRemoved { variant } => {
+                format!("result {} removed", variant)
+            }
+            SchemaChange::FieldAdded {
+                variant,
+                path,
+                optional,
+            } => format!(
+                "{}field `{}` of result {} added",
+                if *optional { "optional " } else { "" },
+                path,
+                variant
+            ),
+            SchemaChange::FieldRemoved {
+                variant,
+                path,
+                optional,
+            } => format!(
+                "{}field `{}` of result {} removed",
+                if *optional { "optional " } else { "" },
+                path,
+                variant
+            ),
+            SchemaChange::TypeChanged {
+                variant,
+                path,
+                old,
+                new,
+            } => format!(
+                "field `{}` of result {} changed from {} to {}",
+                display_path(path),
+                variant,
+                old,
+                new
+            ),
+            SchemaChange::OptionalityChanged {
+                variant,
+                path,
+                optional,
+            } => format!(
+                "field `{}` of result {} became {}",
+                display_path(path),
+                variant,
+                if *optional { "optional" } else { "required" }
+            ),
+            SchemaChange::ArgumentAdded {
+                position,
+                name,
+                required,
+            } => format!(
+                "{} argument {} `{}` added",
+                if *required { "required" } else { "optional" },
+                position,
+                name
+            ),
+            SchemaChange::ArgumentRemoved { position, name } => {
+                format!("argument {} `{}` removed", position, name)
+            }
+            SchemaChange::ArgumentRenamed { position, old, new } => {
+                format!(
+                    "argument {} renamed from `{}` to `{}`",
+                    position, old, new
+                )
+            }
+            SchemaChange::ArgumentTypeChanged {
+                position,
+                name,
+                old,
+                new,
+            } => format!(
+                "argument {} `{}` changed from {} to {}",
+                position, name, old, new
+            ),
+            SchemaChange::ArgumentRequirednessChanged {
+                position,
+                name,
+                required,
+            } => format!(
+                "argument {} `{}` became {}",
+                position,
+                name,
+                if *required { "required" } else { "optional" }
+            ),
+        }
+    }
+}
+
+// The result itself has the empty path.
+fn display_path(path: &str) -> &str {
+    if path.is_empty() {
+        "(result)"
+    } else {
+        path
+    }
+}
+
+/// The changes from `old` to `new` of every command in either, by command.
+/// Commands without changes are left out.
+pub fn diff_commands(
+    old: &[Command],
+    new: &[Command],
+) -> BTreeMap<String, Vec<SchemaChange>> {
+    let mut diff = BTreeMap::new();
+    for command in old {
+        let changes = match new.iter().find(|c| c.name == command.name) {
+            Some(newer) => diff_command(command, newer),
+            None => vec![SchemaChange::CommandRemoved],
+        };
+        if !changes.is_empty() {
+            diff.insert(command.name.clone(), changes);
+        }
+    }
+    for command in new {
+        if !old.iter().any(|c| c.name == command.name) {
+            diff.insert(command.name.clone(), vec![SchemaChange::CommandAdded]);
+        }
+    }
+    diff
+}
+
+pub fn diff_command(old: &Command, new: &Command) -> Vec<SchemaChange> {
+    let mut changes = vec![];
+    // Without recorded arguments on either side there is nothing to compare.
+    if !old.metadata.is_null() && !new.metadata.is_null() {
+        diff_arguments(&old.arguments, &new.arguments, &mut changes);
+    }
+    for (variant, (old_shape, new_shape)) in
+        old.results.iter().zip(&new.results).enumerate()
+    {
+        diff_variant(variant, old_shape, new_shape, &mut changes);
+    }
+    for variant in new.results.len()..old.results.len() {
+        changes.push(SchemaChange::VariantRemoved { variant });
+    }
+    for variant in old.results.len()..new.results.len() {
+        changes.push(SchemaChange::VariantAdded { variant });
+    }
+    changes
+}
+
+fn diff_arguments(
+    old: &[Argument],
+    new: &[Argument],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for (index, (old, new)) in old.iter().zip(new).enumerate() {
+        let position = index + 1;
+        if old.name != new.name {
+            changes.push(SchemaChange::ArgumentRenamed {
+                position,
+                old: old.name.clone(),
+                new: new.name.clone(),
+            });
+        }
+        if old.kind != new.kind {
+            changes.push(SchemaChange::ArgumentTypeChanged {
+                position,
+                name: new.name.clone(),
+                old: old.kind.clone(),
+                new: new.kind.clone(),
+            });
+        }
+        if old.required != new.required {
+            changes.push(SchemaChange::ArgumentRequirednessChanged {
+                position,
+                name: new.name.clone(),
+                required: new.required,
+            });
+        }
+    }
+    for (index, argument) in old.iter().enumerate().skip(new.len()) {
+        changes.push(SchemaChange::ArgumentRemoved {
+            position: index + 1,
+            name: argument.name.clone(),
+        });
+    }
+    for (index, argument) in new.iter().enumerate().skip(old.len()) {
+        changes.push(SchemaChange::ArgumentAdded {
+            position: index + 1,
+            name: argument.name.clone(),
+            required: argument.required,
+        });
+    }
+}
+
+fn diff_variant(
+    variant: usize,
+    old: &Shape,
+    new: &Shape,
+    changes: &mut Vec<SchemaChange>,
+) {
+    let old_nodes = flatten(old);
+    let new_nodes = flatten(new);
+    for (path, (old_type, old_optional)) in &old_nodes {
+        match new_nodes.get(path) {
+            // Fields under a node that came, went or changed type are
+            // reported with it.
+            _ if has_changed_ancestor(path, &old_nodes, &new_nodes) => (),
+            Some((new_type, new_optional)) => {
+                if old_type != new_type {
+                    changes.push(SchemaChange::TypeChanged {
+                        variant,
+                        path: path.clone(),
+                        old: old_type.clone(),
+                        new: new_type.clone(),
+                    });
+                }
+                if old_optional != new_optional {
+                    changes.push(SchemaChange::OptionalityChanged {
+                        variant,
+                        path: path.clone(),
+                        optional: *new_optional,
+                    });
+                }
+            }
+            None => changes.push(SchemaChange::FieldRemoved {
+                variant,
+                path: path.clone(),
+                optional: *old_optional,
+            }),
+        }
+    }
+    for (path, (_, optional)) in &new_nodes {
+        if !old_nodes.contains_key(path)
+            && !has_changed_ancestor(path, &old_nodes, &new_nodes)
+        {
+            changes.push(SchemaChange::FieldAdded {
+                variant,
+                path: path.clone(),
+                optional: *optional,
+            });
+        }
+    }
+}
+
+type Nodes = BTreeMap<String, (String, bool)>;
+
+// Whether a node `path` is nested in was added, removed or changed type.
+fn has_changed_ancestor(path: &str, old: &Nodes, new: &Nodes) -> bool {
+    old.keys().chain(new.keys()).any(|ancestor| {
+        is_nested_in(path, ancestor)
+            && old.get(ancestor).map(|(label, _)| label)
+                != new.get(ancestor).map(|(label, _)| label)
+    })
+}
+
+fn is_nested_in(path: &str, ancestor: &str) -> bool {
+    path != ancestor
+        && path.starts_with(ancestor)
+        && (ancestor.is_empty()
+            || path[ancestor.len()..].starts_with(['.', '[']))
+}
+
+/// Every node of `shape` by path, e.g. `tx[].vin[].txid`, with its type
+/// and whether it is optional.  Map entries are `<key>` in the path, after
+/// the key zcashd documents them with.
+pub fn flatten(shape: &Shape) -> BTreeMap<String, (String, bool)> {
+    let mut nodes = BTreeMap::new();
+    flatten_into(shape, String::new(), false, &mut nodes);
+    nodes
+}
+
+fn flatten_into(
+    shape: &Shape,
+    path: String,
+    optional: bool,
+    nodes: &mut BTreeMap<String, (String, bool)>,
+) {
+    nodes.insert(path.clone(), (type_label(shape), optional));
+    match shape {
+        Shape::Array(item) => {
+            flatten_into(item, format!("{}[]", path), false, nodes)
+        }
+        Shape::Map(key, value) => flatten_into(
+            value,
+            join_path(&path, &format!("<{}>", key)),
+            false,
+            nodes,
+        ),
+        Shape::Object(fields) => {
+            for field in fields {
+                flatten_into(
+                    &field.shape,
+                    join_path(&path, &field.name),
+                    field.optional,
+                    nodes,
+                );
+            }
+        }
+        _ => (),
+    }
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// The type of a node, without what it holds.
+pub fn type_label(shape: &Shape) -> String {
+    match shape {
+        Shape::Decimal => "numeric".to_string(),
+        Shape::String => "string".to_string(),
+        Shape::Bool => "boolean".to_string(),
+        Shape::Hexadecimal => "hexadecimal".to_string(),
+        Shape::Insufficient => "insufficient".to_string(),
+        Shape::Enum(values) => format!("enum({})", values.join(", ")),
+        Shape::Array(_) => "array".to_string(),
+        Shape::Map(..) => "map".to_string(),
+        Shape::Object(_) => "object".to_string(),
+    }
+}
+
+/// The most severe compatibility of the changes in `diff`, for consumers
+/// and for producers.
+pub fn verdict(
+    diff: &BTreeMap<String, Vec<SchemaChange>>,
+) -> (Compatibility, Compatibility) {
+    let changes = diff.values().flatten();
+    (
+        changes
+            .clone()
+            .map(SchemaChange::for_consumers)
+            .max()
+            .unwrap_or(Compatibility::Unaffected),
+        changes
+            .map(SchemaChange::for_producers)
+            .max()
+            .unwrap_or(Compatibility::Unaffected),
+    )
+}
+
+pub fn diff_to_json(diff: &BTreeMap<String, Vec<SchemaChange>>) -> Value {
+    let (consumers, producers) = verdict(diff);
+    let commands = diff
+        .iter()
+        .map(|(command, changes)| {
+            (
+                command.clone(),
+                changes.iter().map(SchemaChange::to_json).collect::<Value>(),
+            )
+        })
+        .collect::<serde_json::Map<String, Value>>();
+    json!({
+        "verdict": {
+            "consumers": consumers.name(),
+            "producers": producers.name(),
+            "breaking": consumers.max(producers) == Compatibility::Breaking,
+            "breaking_changes": diff
+                .values()
+                .flatten()
+                .filter(|change| change.is_breaking())
+                .count(),
+        },
+        "commands": commands,
+    })
+}
+
+/// A Markdown report of `diff`, a section per command.
+pub fn diff_markdown(
+    diff: &BTreeMap<String, Vec<SchemaChange>>,
+    old: &str,
+    new: &str,
+) -> String {
+    let (consumers, producers) = verdict(diff);
+    let mut markdown = format!(
+        "# Changes from {} to {}\n\n\
+         Response consumers: {}. Request producers: {}.\n",
+        old,
+        new,
+        consumers.name(),
+        producers.name()
+    );
+    if diff.is_empty() {
+        markdown.push_str("\nNo changes.\n");
+    }
+    for (command, changes) in diff {
+        markdown.push_str(&format!("\n## `{}`\n\n", command));
+        for change in changes {
+            let mut breaks = vec![];
+            if change.for_consumers() == Compatibility::Breaking {
+                breaks.push("consumers");
+            }
+            if change.for_producers() == Compatibility::Breaking {
+                breaks.push("producers");
+            }
+            if breaks.is_empty() {
+                markdown.push_str(&format!("- {}\n", change.describe()));
+            } else {
+                markdown.push_str(&format!(
+                    "- {} (**breaks {}**)\n",
+                    change.describe(),
+                    breaks.join(" and ")
+                ));
+            }
+        }
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+
+    fn command(interpretation: Value, arguments: Value) -> Command {
+        Command::from_json(
+            "getblock",
+            &interpretation,
+            json!({ "arguments": arguments }),
+        )
+    }
+
+    #[test]
+    fn diff_command_fields_types_and_arguments() {
+        let old = command(
+            json!([{"hash": "hexadecimal", "size": "Decimal",
+                    "Option<tx>": [{"txid": "String"}]}]),
+            json!([{"name": "hash", "type": "string", "required": true}]),
+        );
+        let new = command(
+            json!([
+                {"hash": "hexadecimal", "size": "String", "tx": ["String"],
+                 "Option<chainwork>": "hexadecimal"},
+                "hexadecimal"
+            ]),
+            json!([
+                {"name": "blockhash", "type": "string", "required": true},
+                {"name": "verbosity", "type": "numeric", "required": false}
+            ]),
+        );
+        assert_eq!(
+            diff_command(&old, &new),
+            vec![
+                SchemaChange::ArgumentRenamed {
+                    position: 1,
+                    old: "hash".to_string(),
+                    new: "blockhash".to_string()
+                },
+                SchemaChange::ArgumentAdded {
+                    position: 2,
+                    name: "verbosity".to_string(),
+                    required: false
+                },
+                SchemaChange::TypeChanged {
+                    variant: 0,
+                    path: "size".to_string(),
+                    old: "numeric".to_string(),
+                    new: "string".to_string()
+                },
+                SchemaChange::OptionalityChanged {
+                    variant: 0,
+                    path: "tx".to_string(),
+                    optional: false
+                },
+                SchemaChange::TypeChanged {
+                    variant: 0,
+                    path: "tx[]".to_string(),
+                    old: "object".to_string(),
+                    new: "string".to_string()
+                },
+                SchemaChange::FieldAdded {
+                    variant: 0,
+                    path: "chainwork".to_string(),
+                    optional: true
+                },
+                SchemaChange::VariantAdded { variant: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_commands_added_and_removed() {
+        let getblock = command(json!(["hexadecimal"]), json!([]));
+        let mut getinfo = command(json!([{"version": "Decimal"}]), json!([]));
+        getinfo.name = "getinfo".to_string();
+        let diff = diff_commands(&[getblock], &[getinfo]);
+        assert_eq!(diff["getblock"], vec![SchemaChange::CommandRemoved]);
+        assert_eq!(diff["getinfo"], vec![SchemaChange::CommandAdded]);
+        assert!(diff_markdown(&diff, "v4.3.0", "v4.4.0").contains(
+            "## `getblock`\n\n- command removed (**breaks \
+                       consumers and producers**)\n"
+        ));
+    }
+
+    #[test]
+    fn diff_command_optional_object_removed() {
+        let old = command(
+            json!([{"hash": "hexadecimal",
+                    "Option<tx>": [{"txid": "String", "vout": "Decimal"}]}]),
+            json!([]),
+        );
+        let new = command(json!([{"hash": "hexadecimal"}]), json!([]));
+        let mut diff = BTreeMap::new();
+        diff.insert("getblock".to_string(), diff_command(&old, &new));
+        assert_eq!(
+            diff["getblock"],
+            vec![SchemaChange::FieldRemoved {
+                variant: 0,
+                path: "tx".to_string(),
+                optional: true
+            }]
+        );
+        assert_eq!(
+            verdict(&diff),
+            (Compatibility::Unaffected, Compatibility::Unaffected)
+        );
+        // Nor is what comes with a new field reported apart from it.
+        diff.insert("getblock".to_string(), diff_command(&new, &old));
+        assert_eq!(diff["getblock"].len(), 1);
+        assert_eq!(diff_to_json(&diff)["verdict"]["breaking_changes"], 0);
+    }
+
+    #[test]
+    fn verdict_breaking_for_each_side() {
+        let old = command(
+            json!([{"hash": "hexadecimal", "Option<size>": "Decimal"}]),
+            json!([{"name": "blockhash", "type": "string", "required": true}]),
+        );
+        let new = command(
+            json!([{"hash": "hexadecimal", "Option<chainwork>": "hexadecimal"}]),
+            json!([
+                {"name": "blockhash", "type": "string", "required": true},
+                {"name": "verbosity", "type": "numeric", "required": false}
+            ]),
+        );
+        let mut diff = BTreeMap::new();
+        diff.insert("getblock".to_string(), diff_command(&old, &new));
+        // Only optional things came and went.
+        assert_eq!(
+            verdict(&diff),
+            (Compatibility::Additive, Compatibility::Additive)
+        );
+        let required = command(
+            json!([{"hash": "String"}]),
+            json!([
+                {"name": "blockhash", "type": "string", "required": true},
+                {"name": "verbosity", "type": "numeric", "required": true}
+            ]),
+        );
+        diff.insert("getblock".to_string(), diff_command(&new, &required));
+        assert_eq!(
+            verdict(&diff),
+            (Compatibility::Breaking, Compatibility::Breaking)
+        );
+        let json = diff_to_json(&diff);
+        assert_eq!(json["verdict"]["breaking"], true);
+        assert_eq!(json["verdict"]["breaking_changes"], 2);
+        assert_eq!(
+            json["commands"]["getblock"][0],
+            json!({"kind": "argument_requiredness_changed", "position": 2,
+                   "name": "verbosity", "required": true,
+                   "consumers": "unaffected", "producers": "breaking"})
+        );
+    }
+}
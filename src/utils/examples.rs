@@ -0,0 +1,91 @@
+use serde_json::{json, Value};
+
+/// Turns the `"examples"` section produced by `partition_help_text` into one
+/// `{"line", "params"}` per `> ` example, where `params` are the positional
+/// parameters the example passes, or `null` if they can't be made out.
+///
+/// `zcash-cli` parameters are read as JSON where they parse as JSON and as
+/// strings otherwise; `curl` examples carry their JSON-RPC request.
+pub fn structure_examples(examples_section: &str) -> Value {
+    examples_section
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("> "))
+        .map(|line| {
+            let words = split_shell_words(line);
+            let params = match words.first().map(String::as_str) {
+                Some("zcash-cli") => Some(
+                    words
+                        .iter()
+                        .skip(2)
+                        .map(|word| {
+                            serde_json::from_str(word)
+                                .unwrap_or_else(|_| json!(word))
+                        })
+                        .collect::<Vec<Value>>(),
+                ),
+                Some("curl") => words
+                    .iter()
+                    .skip_while(|word| *word != "--data-binary")
+                    .nth(1)
+                    .and_then(|request| {
+                        serde_json::from_str::<Value>(request).ok()
+                    })
+                    .and_then(|request| request["params"].as_array().cloned()),
+                _ => None,
+            };
+            json!({"line": line, "params": params})
+        })
+        .collect()
+}
+
+// Splits `line` into words as a POSIX shell would, for the single and
+// double quoting zcashd's examples use.
+fn split_shell_words(line: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.push(c),
+            (None, '\'') | (None, '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use crate::utils::test;
+
+    #[test]
+    fn structure_examples_z_sendmany() {
+        let examples = structure_examples(test::Z_SENDMANY_EXAMPLES);
+        assert_eq!(
+            examples[0]["params"],
+            json!([
+                "t1M72Sfpbz1BPpXFHz9m3CdqATR44Jvaydd",
+                [{"address": "ztfaW34Gj9FrnGUEf833ywDVL62NWXBM81u6EQnM6VR45eYnXhwztecW1SjxA7JrmAXKJhxhj3vDNEpVCQoSvVoSpmbhtjf", "amount": 5.0}]
+            ])
+        );
+        assert_eq!(examples[1]["params"], examples[0]["params"]);
+    }
+}
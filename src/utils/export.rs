@@ -0,0 +1,22 @@
+//! Exporters turning the interpretations loaded by `model` into schemas and
+//! code for other languages and tools.
+pub mod go;
+pub mod json_schema;
+pub mod openrpc;
+pub mod protobuf;
+pub mod python;
+pub mod reference;
+pub mod rust_client;
+pub mod rust_types;
+pub mod typescript;
+
+use std::path::Path;
+
+/// Writes `contents` to `file_name` in `destination`, creating it if need
+/// be.
+pub fn write_export(destination: &Path, file_name: &str, contents: &str) {
+    std::fs::create_dir_all(destination)
+        .expect("Couldn't create export directory!");
+    std::fs::write(destination.join(file_name), contents)
+        .expect("panic during fs::write export!");
+}
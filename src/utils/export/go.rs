@@ -0,0 +1,347 @@
+use crate::utils::export::rust_types::type_name;
+use crate::utils::model::{Command, Field, Shape};
+use std::collections::{BTreeMap, HashSet};
+
+pub const GO_PACKAGE: &str = "zcashd";
+
+// Units of numbers that are always whole; other numbers are `float64`.
+const INTEGER_UNITS: [&str; 6] = [
+    "zatoshi",
+    "bytes",
+    "kilobytes",
+    "seconds",
+    "milliseconds",
+    "unix_timestamp_seconds",
+];
+
+/// Go structs for the response of every command in `commands`, named like
+/// `rust_types` names its types, as one file per category, e.g.
+/// `blockchain.go`.  Optional fields are pointers tagged `omitempty`, and
+/// responses with several result variants are left raw to be decoded as one
+/// of the `<Command>Response<i>` types.
+pub fn go_files(commands: &[Command], source: &str) -> Vec<(String, String)> {
+    let mut types = GoTypes::default();
+    let mut files: BTreeMap<String, (Vec<String>, bool)> = BTreeMap::new();
+    for command in commands {
+        let category = command.category.as_deref().unwrap_or("uncategorized");
+        types.declare_response(command);
+        let file = files
+            .entry(format!("{}.go", file_stem(category)))
+            .or_default();
+        file.0.append(&mut types.definitions);
+        file.1 |= std::mem::take(&mut types.raw);
+    }
+    files
+        .into_iter()
+        .map(|(name, (definitions, raw))| {
+            let imports = if raw {
+                "\nimport \"encoding/json\"\n"
+            } else {
+                ""
+            };
+            (
+                name,
+                format!(
+                    "// Code generated by quizface from `{}`. DO NOT EDIT.\n\n\
+                     package {}\n{}{}",
+                    source,
+                    GO_PACKAGE,
+                    imports,
+                    definitions.concat()
+                ),
+            )
+        })
+        .collect()
+}
+
+// `Raw transactions` -> `raw_transactions`
+fn file_stem(category: &str) -> String {
+    category
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>()
+        .join("_")
+}
+
+#[derive(Default)]
+struct GoTypes {
+    /// Those of the command being declared.
+    definitions: Vec<String>,
+    declared: HashSet<String>,
+    /// Whether those definitions use `json.RawMessage`.
+    raw: bool,
+}
+
+impl GoTypes {
+    fn declare_response(&mut self, command: &Command) {
+        let name = format!("{}Response", type_name(&command.name));
+        self.declared.insert(name.clone());
+        let mut doc =
+            vec![format!("{} is the response of `{}`.", name, command.name)];
+        if let Some(summary) = &command.summary {
+            doc.push(String::new());
+            doc.extend(summary.lines().map(String::from));
+        }
+        if command.deprecated {
+            doc.push(String::new());
+            doc.push(format!(
+                "Deprecated: zcashd deprecates `{}`.",
+                command.name
+            ));
+        }
+        match command.results.len() {
+            0 => {
+                self.raw = true;
+                self.definitions.push(format!(
+                    "\n{}type {} = json.RawMessage\n",
+                    comment(&doc, ""),
+                    name
+                ));
+            }
+            1 => self.declare_named(&name, &command.results[0], &doc),
+            _ => {
+                let variants = (0..command.results.len())
+                    .map(|index| format!("`{}{}`", name, index))
+                    .collect::<Vec<String>>();
+                doc.push(String::new());
+                doc.push(format!(
+                    "It decodes as one of {}, depending on the params.",
+                    variants.join(", ")
+                ));
+                self.raw = true;
+                self.definitions.push(format!(
+                    "\n{}type {} = json.RawMessage\n",
+                    comment(&doc, ""),
+                    name
+                ));
+                for (index, shape) in command.results.iter().enumerate() {
+                    let variant = format!("{}{}", name, index);
+                    let doc = match command.result_conditions.get(index) {
+                        Some(Some(condition)) => format!(
+                            "{} is the response of `{}` for {}.",
+                            variant, command.name, condition
+                        ),
+                        _ => format!(
+                            "{} is result variant {} of `{}`.",
+                            variant, index, command.name
+                        ),
+                    };
+                    self.declare_named(&variant, shape, &[doc]);
+                }
+            }
+        }
+    }
+
+    // Declares `name` as the type of `shape`: a struct if it is an object, a
+    // named type otherwise.
+    fn declare_named(&mut self, name: &str, shape: &Shape, doc: &[String]) {
+        self.declared.insert(name.to_string());
+        match shape {
+            Shape::Object(fields) => self.declare_struct(name, fields, doc),
+            Shape::Enum(values) => self.declare_enum(name, values, doc),
+            shape => {
+                let go_type = self.go_type(shape, None, name);
+                self.definitions.push(format!(
+                    "\n{}type {} {}\n",
+                    comment(doc, ""),
+                    name,
+                    go_type
+                ));
+            }
+        }
+    }
+
+    fn declare_struct(&mut self, name: &str, fields: &[Field], doc: &[String]) {
+        self.declared.insert(name.to_string());
+        let mut members = String::new();
+        let mut names: Vec<String> = vec![];
+        for field in fields {
+            let mut member = type_name(&field.name);
+            if !member.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                member.insert(0, 'F');
+            }
+            let mut unique = member.clone();
+            let mut suffix = 1;
+            while names.contains(&unique) {
+                suffix += 1;
+                unique = format!("{}{}", member, suffix);
+            }
+            names.push(unique.clone());
+            let mut go_type = self.go_type(
+                &field.shape,
+                field.unit.as_deref(),
+                &format!("{}{}", name, type_name(&field.name)),
+            );
+            let mut tag = field.name.clone();
+            if field.optional {
+                tag.push_str(",omitempty");
+                // Absent and zero are told apart by a nil pointer.
+                if !go_type.starts_with("[]")
+                    && !go_type.starts_with("map[")
+                    && go_type != "json.RawMessage"
+                {
+                    go_type.insert(0, '*');
+                }
+            }
+            if let Some(description) = &field.description {
+                members.push_str(&comment(
+                    &description.lines().map(String::from).collect::<Vec<_>>(),
+                    "\t",
+                ));
+            }
+            members.push_str(&format!(
+                "\t{} {} `json:\"{}\"`\n",
+                unique,
+                go_type,
+                tag.escape_default()
+            ));
+        }
+        let doc = if doc.is_empty() {
+            vec![format!("{} is an object nested in a response.", name)]
+        } else {
+            doc.to_vec()
+        };
+        self.definitions.push(format!(
+            "\n{}type {} struct {{\n{}}}\n",
+            comment(&doc, ""),
+            name,
+            members
+        ));
+    }
+
+    fn declare_enum(&mut self, name: &str, values: &[String], doc: &[String]) {
+        self.declared.insert(name.to_string());
+        let mut constants = String::new();
+        for value in values {
+            constants.push_str(&format!(
+                "\t{}{} {} = {:?}\n",
+                name,
+                type_name(value),
+                name,
+                value
+            ));
+        }
+        let doc = if doc.is_empty() {
+            vec![format!("{} is one of the values below.", name)]
+        } else {
+            doc.to_vec()
+        };
+        self.definitions.push(format!(
+            "\n{}type {} string\n\nconst (\n{})\n",
+            comment(&doc, ""),
+            name,
+            constants
+        ));
+    }
+
+    // The Go type of `shape`, declaring any type it needs as `name`.
+    fn go_type(
+        &mut self,
+        shape: &Shape,
+        unit: Option<&str>,
+        name: &str,
+    ) -> String {
+        match shape {
+            Shape::Decimal => {
+                if unit.is_some_and(|unit| INTEGER_UNITS.contains(&unit)) {
+                    "int64".to_string()
+                } else {
+                    "float64".to_string()
+                }
+            }
+            Shape::String | Shape::Hexadecimal => "string".to_string(),
+            Shape::Bool => "bool".to_string(),
+            Shape::Insufficient => {
+                self.raw = true;
+                "json.RawMessage".to_string()
+            }
+            Shape::Enum(values) => {
+                let name = self.unique(name);
+                self.declare_enum(&name, values, &[]);
+                name
+            }
+            Shape::Array(item) => format!(
+                "[]{}",
+                self.go_type(item, unit, &format!("{}Item", name))
+            ),
+            Shape::Map(_, value) => format!(
+                "map[string]{}",
+                self.go_type(value, unit, &format!("{}Entry", name))
+            ),
+            Shape::Object(fields) => {
+                let name = self.unique(name);
+                self.declare_struct(&name, fields, &[]);
+                name
+            }
+        }
+    }
+
+    fn unique(&self, name: &str) -> String {
+        let mut unique = name.to_string();
+        let mut suffix = 1;
+        while self.declared.contains(&unique) {
+            suffix += 1;
+            unique = format!("{}{}", name, suffix);
+        }
+        unique
+    }
+}
+
+fn comment(lines: &[String], indent: &str) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                format!("{}//\n", indent)
+            } else {
+                format!("{}// {}\n", indent, line.trim())
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn go_files_by_category() {
+        let getinfo = Command::from_json(
+            "getinfo",
+            &json!([{"Option<proxy>": "String", "balance": "Decimal",
+                     "timeoffset": "Decimal"}]),
+            json!({
+                "category": "Control",
+                "fields": [{
+                    "/balance": {"description": "the balance", "unit": "ZEC"},
+                    "/timeoffset": {"unit": "seconds"}
+                }]
+            }),
+        );
+        let getblock = Command::from_json(
+            "getblock",
+            &json!(["hexadecimal", {"hash": "hexadecimal"}]),
+            json!({"category": "Blockchain"}),
+        );
+        let files = go_files(&[getinfo, getblock], "test");
+        assert_eq!(files[0].0, "blockchain.go");
+        assert!(files[0].1.contains("\nimport \"encoding/json\"\n"));
+        assert!(files[0]
+            .1
+            .contains("type GetblockResponse = json.RawMessage\n"));
+        assert!(files[0].1.contains(
+            "// GetblockResponse0 is result variant 0 of `getblock`.\n\
+             type GetblockResponse0 string\n"
+        ));
+        assert_eq!(files[1].0, "control.go");
+        assert!(files[1].1.contains(
+            "type GetinfoResponse struct {\n\
+             \tProxy *string `json:\"proxy,omitempty\"`\n\
+             \t// the balance\n\
+             \tBalance float64 `json:\"balance\"`\n\
+             \tTimeoffset int64 `json:\"timeoffset\"`\n}\n"
+        ));
+    }
+}
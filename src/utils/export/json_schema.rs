@@ -0,0 +1,119 @@
+use crate::utils::model::{Command, Shape};
+use serde_json::{json, map::Map, Value};
+
+pub const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// A JSON Schema for the response of `command`, with one `oneOf` branch per
+/// result variant.
+pub fn command_schema(command: &Command) -> Value {
+    let mut schema = json!({
+        "$schema": DIALECT,
+        "$id": format!("{}.schema.json", command.name),
+        "title": command.name,
+    });
+    if let Some(summary) = &command.summary {
+        schema["description"] = json!(summary);
+    }
+    if command.deprecated {
+        schema["deprecated"] = json!(true);
+    }
+    // No documented result: any response is valid.
+    if !command.results.is_empty() {
+        schema["oneOf"] =
+            command.results.iter().map(shape_schema).collect::<Value>();
+    }
+    schema
+}
+
+pub fn shape_schema(shape: &Shape) -> Value {
+    match shape {
+        Shape::Decimal => json!({"type": "number"}),
+        Shape::String => json!({"type": "string"}),
+        Shape::Bool => json!({"type": "boolean"}),
+        Shape::Hexadecimal => {
+            json!({"type": "string", "pattern": "^[0-9a-fA-F]*$"})
+        }
+        Shape::Insufficient => json!({}),
+        Shape::Enum(values) => json!({"enum": values}),
+        Shape::Array(item) => {
+            json!({"type": "array", "items": shape_schema(item)})
+        }
+        Shape::Map(_, value) => json!({
+            "type": "object",
+            "additionalProperties": shape_schema(value),
+        }),
+        Shape::Object(fields) => {
+            let mut properties = Map::new();
+            for field in fields {
+                let mut property = shape_schema(&field.shape);
+                if let Some(description) = &field.description {
+                    property["description"] = json!(description);
+                }
+                properties.insert(field.name.clone(), property);
+            }
+            json!({
+                "type": "object",
+                "properties": properties,
+                "required": fields
+                    .iter()
+                    .filter(|field| !field.optional)
+                    .map(|field| field.name.as_str())
+                    .collect::<Vec<&str>>(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+
+    #[test]
+    fn command_schema_getinfo_fragment() {
+        let command = Command::from_json(
+            "getinfo",
+            &json!([{"Option<proxy>": "String", "version": "Decimal"}]),
+            json!({
+                "description": {"summary": "Returns an object."},
+                "fields": [{"/version": {"description": "the server version"}}]
+            }),
+        );
+        assert_eq!(
+            command_schema(&command),
+            json!({
+                "$schema": DIALECT,
+                "$id": "getinfo.schema.json",
+                "title": "getinfo",
+                "description": "Returns an object.",
+                "oneOf": [{
+                    "type": "object",
+                    "properties": {
+                        "proxy": {"type": "string"},
+                        "version": {
+                            "type": "number",
+                            "description": "the server version"
+                        }
+                    },
+                    "required": ["version"]
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn shape_schema_map_of_arrays() {
+        assert_eq!(
+            shape_schema(&Shape::Map(
+                "transactionid".to_string(),
+                Box::new(Shape::Array(Box::new(Shape::Hexadecimal)))
+            )),
+            json!({
+                "type": "object",
+                "additionalProperties": {
+                    "type": "array",
+                    "items": {"type": "string", "pattern": "^[0-9a-fA-F]*$"}
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use crate::utils::export::json_schema::shape_schema;
+use crate::utils::model::{Argument, Command};
+use serde_json::{json, Value};
+
+pub const OPENRPC_VERSION: &str = "1.2.6";
+
+/// One OpenRPC document describing every command in `commands`, as
+/// documented by `zcashd_version`.
+pub fn openrpc_document(commands: &[Command], zcashd_version: &str) -> Value {
+    json!({
+        "openrpc": OPENRPC_VERSION,
+        "info": {"title": "zcashd", "version": zcashd_version},
+        "methods": commands.iter().map(method).collect::<Vec<Value>>(),
+    })
+}
+
+fn method(command: &Command) -> Value {
+    let mut method = json!({
+        "name": command.name,
+        "paramStructure": "by-position",
+        "params": command.arguments.iter().map(param).collect::<Vec<Value>>(),
+        "result": {
+            "name": format!("{}_result", command.name),
+            "schema": if command.results.is_empty() {
+                json!({})
+            } else {
+                json!({
+                    "oneOf": command
+                        .results
+                        .iter()
+                        .map(shape_schema)
+                        .collect::<Vec<Value>>()
+                })
+            },
+        },
+    });
+    if let Some(summary) = &command.summary {
+        method["summary"] = json!(summary);
+    }
+    if let Some(body) = command.metadata["description"]["body"].as_array() {
+        if !body.is_empty() {
+            method["description"] = json!(body
+                .iter()
+                .filter_map(|paragraph| paragraph.as_str())
+                .collect::<Vec<&str>>()
+                .join("\n\n"));
+        }
+    }
+    let mut tags = vec![];
+    if let Some(category) = &command.category {
+        tags.push(json!({"name": category}));
+    }
+    if command.experimental {
+        tags.push(json!({"name": "experimental"}));
+    }
+    if !tags.is_empty() {
+        method["tags"] = Value::Array(tags);
+    }
+    if command.deprecated {
+        method["deprecated"] = json!(true);
+    }
+    let mut examples: Vec<Value> = vec![];
+    for example in &command.examples {
+        if let Some(params) = &example.params {
+            let params = params
+                .iter()
+                .enumerate()
+                .map(|(position, value)| {
+                    json!({
+                        "name": command
+                            .arguments
+                            .get(position)
+                            .map_or(format!("param{}", position + 1), |a| {
+                                a.name.clone()
+                            }),
+                        "value": value,
+                    })
+                })
+                .collect::<Vec<Value>>();
+            // `zcash-cli` and `curl` examples usually pass the same params.
+            if !examples.iter().any(|e| e["params"] == json!(params)) {
+                examples.push(json!({
+                    "name": format!("{}_example_{}", command.name, examples.len() + 1),
+                    "description": example.line,
+                    "params": params,
+                }));
+            }
+        }
+    }
+    if !examples.is_empty() {
+        method["examples"] = Value::Array(examples);
+    }
+    method
+}
+
+fn param(argument: &Argument) -> Value {
+    let mut schema = shape_schema(&argument.shape());
+    if let Some(default) = &argument.default {
+        schema["default"] =
+            serde_json::from_str(default).unwrap_or_else(|_| json!(default));
+    }
+    let mut param = json!({
+        "name": argument.name,
+        "required": argument.required,
+        "schema": schema,
+    });
+    if !argument.description.is_empty() {
+        param["description"] = json!(argument.description);
+    }
+    param
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use crate::utils::arguments::structure_arguments;
+    use crate::utils::examples::structure_examples;
+    use crate::utils::test;
+
+    #[test]
+    fn openrpc_document_z_sendmany() {
+        let command = Command::from_json(
+            "z_sendmany",
+            &json!(["String"]),
+            json!({
+                "category": "Wallet",
+                "description": {"summary": "Send multiple times.", "body": []},
+                "capabilities": {"experimental": false, "deprecated": false},
+                "arguments": structure_arguments(test::Z_SENDMANY_ARGUMENTS),
+                "examples": structure_examples(test::Z_SENDMANY_EXAMPLES),
+            }),
+        );
+        let document = openrpc_document(&[command], "v4.3.0");
+        let method = &document["methods"][0];
+        assert_eq!(document["info"]["version"], "v4.3.0");
+        assert_eq!(method["tags"], json!([{"name": "Wallet"}]));
+        assert_eq!(
+            method["params"][2],
+            json!({
+                "name": "minconf",
+                "required": false,
+                "description": "Only use funds confirmed at least this many times.",
+                "schema": {"type": "number", "default": 1}
+            })
+        );
+        assert_eq!(method["result"]["schema"]["oneOf"][0]["type"], "string");
+        assert_eq!(method["examples"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            method["examples"][0]["params"][0],
+            json!({
+                "name": "fromaddress",
+                "value": "t1M72Sfpbz1BPpXFHz9m3CdqATR44Jvaydd"
+            })
+        );
+    }
+}
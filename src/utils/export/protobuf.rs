@@ -0,0 +1,349 @@
+use crate::utils::export::rust_types::{field_name, type_name};
+use crate::utils::model::{Argument, Command, Field, Shape};
+use std::collections::HashSet;
+
+/// A proto3 file with a `<Command>Request` message of the arguments and a
+/// `<Command>Response` message of the result of every command in
+/// `commands`, and a `Zcashd` service with an rpc per command.  Responses
+/// with several result variants are a `oneof` of them; those that aren't an
+/// object carry it as `result`.
+pub fn proto_file(commands: &[Command], source: &str) -> String {
+    let mut messages = Messages::default();
+    let mut service = "\nservice Zcashd {\n".to_string();
+    for command in commands {
+        messages.declare_request(command);
+        messages.declare_response(command);
+        let name = type_name(&command.name);
+        service.push_str(&comment(
+            &[command
+                .summary
+                .clone()
+                .unwrap_or_else(|| format!("`{}`", command.name))],
+            "  ",
+        ));
+        if command.deprecated {
+            service.push_str(&format!(
+                "  rpc {}({}Request) returns ({}Response) {{\n    \
+                 option deprecated = true;\n  }}\n",
+                name, name, name
+            ));
+        } else {
+            service.push_str(&format!(
+                "  rpc {}({}Request) returns ({}Response);\n",
+                name, name, name
+            ));
+        }
+    }
+    service.push_str("}\n");
+    format!(
+        "// zcashd RPC service generated by quizface from `{}`.\n\
+         // Do not edit.\n\
+         syntax = \"proto3\";\n\n\
+         package zcashd;\n\n\
+         import \"google/protobuf/struct.proto\";\n{}{}",
+        source,
+        messages.definitions.concat(),
+        service
+    )
+}
+
+#[derive(Default)]
+struct Messages {
+    definitions: Vec<String>,
+    declared: HashSet<String>,
+}
+
+impl Messages {
+    fn declare_request(&mut self, command: &Command) {
+        let name = format!("{}Request", type_name(&command.name));
+        self.declared.insert(name.clone());
+        let mut body = String::new();
+        // Without recorded arguments the params are left to the caller.
+        if command.metadata.is_null() {
+            body.push_str("  repeated google.protobuf.Value params = 1;\n");
+        }
+        let mut names = vec![];
+        for (number, argument) in command.arguments.iter().enumerate() {
+            let name = unique_field(&mut names, &argument.name);
+            if !argument.description.is_empty() {
+                body.push_str(&comment(
+                    &[argument.description.lines().next().unwrap().into()],
+                    "  ",
+                ));
+            }
+            body.push_str(&format!(
+                "  {}{} {} = {};\n",
+                if argument.required { "" } else { "optional " },
+                argument_type(argument),
+                name,
+                number + 1
+            ));
+        }
+        self.definitions.push(format!(
+            "\n// The positional params of `{}`.\nmessage {} {{\n{}}}\n",
+            command.name, name, body
+        ));
+    }
+
+    fn declare_response(&mut self, command: &Command) {
+        let name = format!("{}Response", type_name(&command.name));
+        self.declared.insert(name.clone());
+        let doc = format!("The response of `{}`.", command.name);
+        let body = match command.results.len() {
+            0 => "  google.protobuf.Value result = 1;\n".to_string(),
+            1 => match &command.results[0] {
+                Shape::Object(fields) => self.fields(&name, fields),
+                shape => self.field(shape, &name, "result", 1),
+            },
+            _ => {
+                let mut oneof = "  oneof result {\n".to_string();
+                for (index, shape) in command.results.iter().enumerate() {
+                    let variant = format!("{}{}", name, index);
+                    // A `oneof` can't hold repeated or map fields.
+                    let variant_type = match shape {
+                        Shape::Array(_) | Shape::Map(..) => {
+                            self.wrap(shape, &variant)
+                        }
+                        shape => self.proto_type(shape, &variant),
+                    };
+                    oneof.push_str(&format!(
+                        "    {} v{} = {};\n",
+                        variant_type,
+                        index,
+                        index + 1
+                    ));
+                }
+                oneof.push_str("  }\n");
+                oneof
+            }
+        };
+        self.definitions.push(format!(
+            "\n{}message {} {{\n{}}}\n",
+            comment(&[doc], ""),
+            name,
+            body
+        ));
+    }
+
+    fn fields(&mut self, name: &str, fields: &[Field]) -> String {
+        let mut body = String::new();
+        let mut names = vec![];
+        for (number, field) in fields.iter().enumerate() {
+            let member = unique_field(&mut names, &field.name);
+            if let Some(description) = &field.description {
+                body.push_str(&comment(
+                    &description.lines().map(String::from).collect::<Vec<_>>(),
+                    "  ",
+                ));
+            }
+            let mut definition = self.field(
+                &field.shape,
+                &format!("{}{}", name, type_name(&field.name)),
+                &member,
+                number + 1,
+            );
+            if field.optional
+                && !definition.starts_with("  repeated ")
+                && !definition.starts_with("  map<")
+            {
+                definition.insert_str(2, "optional ");
+            }
+            if member != field.name {
+                // Keep the zcashd key in the JSON mapping.
+                definition.truncate(definition.len() - 2);
+                definition.push_str(&format!(
+                    " [json_name = \"{}\"];\n",
+                    field.name.escape_default()
+                ));
+            }
+            body.push_str(&definition);
+        }
+        body
+    }
+
+    // A field `member` numbered `number` of the type of `shape`, declaring
+    // any message or enum it needs as `name`.
+    fn field(
+        &mut self,
+        shape: &Shape,
+        name: &str,
+        member: &str,
+        number: usize,
+    ) -> String {
+        let (label, proto_type) = match shape {
+            Shape::Array(item) => {
+                let item_name = format!("{}Item", name);
+                let item_type = match item.as_ref() {
+                    // Repeated fields don't nest.
+                    Shape::Array(_) | Shape::Map(..) => {
+                        self.wrap(item, &item_name)
+                    }
+                    item => self.proto_type(item, &item_name),
+                };
+                ("repeated ".to_string(), item_type)
+            }
+            Shape::Map(_, value) => {
+                let entry_name = format!("{}Entry", name);
+                let value_type = match value.as_ref() {
+                    // Nor do map values.
+                    Shape::Array(_) | Shape::Map(..) => {
+                        self.wrap(value, &entry_name)
+                    }
+                    value => self.proto_type(value, &entry_name),
+                };
+                (String::new(), format!("map<string, {}>", value_type))
+            }
+            shape => (String::new(), self.proto_type(shape, name)),
+        };
+        format!("  {}{} {} = {};\n", label, proto_type, member, number)
+    }
+
+    // A message named `name` with a repeated or map field of `shape`.
+    fn wrap(&mut self, shape: &Shape, name: &str) -> String {
+        let name = self.unique(name);
+        self.declared.insert(name.clone());
+        let member = match shape {
+            Shape::Map(..) => "entries",
+            _ => "items",
+        };
+        let body = self.field(shape, &name, member, 1);
+        self.definitions
+            .push(format!("\nmessage {} {{\n{}}}\n", name, body));
+        name
+    }
+
+    // The type of a singular field of `shape`, declaring any message or
+    // enum it needs as `name`.
+    fn proto_type(&mut self, shape: &Shape, name: &str) -> String {
+        match shape {
+            Shape::Decimal => "double".to_string(),
+            Shape::String | Shape::Hexadecimal => "string".to_string(),
+            Shape::Bool => "bool".to_string(),
+            Shape::Insufficient => "google.protobuf.Value".to_string(),
+            Shape::Enum(values) => {
+                let name = self.unique(name);
+                self.declared.insert(name.clone());
+                // Enum values are scoped to the package, so are prefixed.
+                let prefix =
+                    field_name(&name).trim_start_matches("r#").to_uppercase();
+                let mut definition = format!(
+                    "\nenum {} {{\n  {}_UNSPECIFIED = 0;\n",
+                    name, prefix
+                );
+                for (number, value) in values.iter().enumerate() {
+                    definition.push_str(&format!(
+                        "  {}_{} = {};\n",
+                        prefix,
+                        field_name(value)
+                            .trim_start_matches("r#")
+                            .to_uppercase(),
+                        number + 1
+                    ));
+                }
+                definition.push_str("}\n");
+                self.definitions.push(definition);
+                name
+            }
+            Shape::Array(_) | Shape::Map(..) => self.wrap(shape, name),
+            Shape::Object(fields) => {
+                let name = self.unique(name);
+                self.declared.insert(name.clone());
+                let body = self.fields(&name, fields);
+                self.definitions
+                    .push(format!("\nmessage {} {{\n{}}}\n", name, body));
+                name
+            }
+        }
+    }
+
+    fn unique(&self, name: &str) -> String {
+        let mut unique = name.to_string();
+        let mut suffix = 1;
+        while self.declared.contains(&unique) {
+            suffix += 1;
+            unique = format!("{}{}", name, suffix);
+        }
+        unique
+    }
+}
+
+fn argument_type(argument: &Argument) -> String {
+    match argument.shape() {
+        Shape::Decimal => "double".to_string(),
+        Shape::String | Shape::Hexadecimal => "string".to_string(),
+        Shape::Bool => "bool".to_string(),
+        Shape::Array(_) => "google.protobuf.ListValue".to_string(),
+        _ => "google.protobuf.Value".to_string(),
+    }
+}
+
+// `scriptPubKey` -> `script_pub_key`, told apart from the fields named
+// before it if they clash.
+fn unique_field(names: &mut Vec<String>, key: &str) -> String {
+    let name = field_name(key).trim_start_matches("r#").to_string();
+    let mut unique = name.clone();
+    let mut suffix = 1;
+    while names.contains(&unique) {
+        suffix += 1;
+        unique = format!("{}_{}", name, suffix);
+    }
+    names.push(unique.clone());
+    unique
+}
+
+fn comment(lines: &[String], indent: &str) -> String {
+    lines
+        .iter()
+        .map(|line| format!("{}// {}\n", indent, line.trim()))
+        .collect()
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn proto_file_getrawmempool_variants() {
+        let command = Command::from_json(
+            "getrawmempool",
+            &json!([
+                ["String"],
+                {"transactionid": {"size": "Decimal", "Option<depends>": ["String"]}}
+            ]),
+            json!({
+                "arguments": [
+                    {"name": "verbose", "type": "boolean",
+                     "required": false, "description": "True for a json object"}
+                ]
+            }),
+        );
+        let proto = proto_file(&[command], "test");
+        assert!(proto.contains(
+            "message GetrawmempoolRequest {\n  \
+             // True for a json object\n  \
+             optional bool verbose = 1;\n}\n"
+        ));
+        assert!(proto.contains(
+            "message GetrawmempoolResponse0 {\n  \
+             repeated string items = 1;\n}\n"
+        ));
+        assert!(proto.contains(
+            "message GetrawmempoolResponse1 {\n  \
+             map<string, GetrawmempoolResponse1Entry> entries = 1;\n}\n"
+        ));
+        assert!(proto.contains(
+            "message GetrawmempoolResponse1Entry {\n  \
+             repeated string depends = 1;\n  double size = 2;\n}\n"
+        ));
+        assert!(proto.contains(
+            "  oneof result {\n    \
+             GetrawmempoolResponse0 v0 = 1;\n    \
+             GetrawmempoolResponse1 v1 = 2;\n  }\n"
+        ));
+        assert!(proto.contains(
+            "  rpc Getrawmempool(GetrawmempoolRequest) \
+             returns (GetrawmempoolResponse);\n"
+        ));
+    }
+}
@@ -0,0 +1,492 @@
+use crate::utils::export::rust_client::variant_method_name;
+use crate::utils::export::rust_types::{field_name, type_name};
+use crate::utils::fields::find_unit;
+use crate::utils::model::{Argument, Command, Field, Shape};
+use std::collections::HashSet;
+
+pub const TYPES_MODULE: &str = "zcashd_types";
+
+const KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break",
+    "class", "continue", "def", "del", "elif", "else", "except", "finally",
+    "for", "from", "global", "if", "import", "in", "is", "lambda", "nonlocal",
+    "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+// Numbers are parsed as `Decimal` so that amounts are exact, and amounts
+// get their own types so they can't be mixed up with each other.
+const TYPES_PRELUDE: &str = r#"from decimal import Decimal
+from typing import Any, Dict, List, Literal, NewType, TypedDict, Union
+
+from typing_extensions import NotRequired
+
+#: A number zcashd documents without a unit.
+Numeric = Union[int, Decimal]
+#: An amount in ZEC.
+Zec = NewType("Zec", Decimal)
+#: An amount in zatoshis, 10^-8 ZEC.
+Zatoshi = NewType("Zatoshi", int)
+#: A fee rate in ZEC per kB.
+ZecPerKb = NewType("ZecPerKb", Decimal)
+"#;
+
+/// A Python module of `TypedDict`s for the response of every command in
+/// `commands`, named like `rust_types` names its types.  Optional fields
+/// are `NotRequired`, and amounts in ZEC or zatoshis typed as such.
+pub fn python_types(commands: &[Command], source: &str) -> String {
+    let mut types = PythonTypes::default();
+    for command in commands {
+        types.declare_response(command);
+    }
+    format!(
+        "\"\"\"zcashd RPC response types generated by quizface from `{}`.\n\n\
+         Do not edit.\n\"\"\"\n{}{}",
+        source,
+        TYPES_PRELUDE,
+        types.definitions.concat()
+    )
+}
+
+/// A Python module with a typed `Zcashd` method per command in `commands`,
+/// and per result variant like `rust_client`, over any function making the
+/// call, e.g. the `http_call` it defines.
+pub fn python_client(commands: &[Command], source: &str) -> String {
+    let mut module = format!(
+        "\"\"\"Typed zcashd RPC calls generated by quizface from `{}`.\n\n\
+         Do not edit.\n\"\"\"\n{}",
+        source,
+        CLIENT_PRELUDE.replace("{types}", TYPES_MODULE)
+    );
+    for command in commands {
+        let response = format!("{}Response", type_name(&command.name));
+        module.push_str(&client_method(command, &command.name, &response));
+        if command.results.len() > 1 {
+            for index in 0..command.results.len() {
+                module.push_str(&client_method(
+                    command,
+                    &variant_method_name(command, index),
+                    &format!("{}{}", response, index),
+                ));
+            }
+        }
+    }
+    module
+}
+
+const CLIENT_PRELUDE: &str = r#"import base64
+import json
+import urllib.request
+from decimal import Decimal
+from typing import Any, Callable, List, Optional, cast
+
+import {types}
+from {types} import Numeric, Zatoshi, Zec, ZecPerKb  # noqa: F401
+
+#: Calls a method with positional params and returns its result.
+Call = Callable[[str, List[Any]], Any]
+
+
+def http_call(url: str, user: str, password: str) -> Call:
+    """Calls zcashd's JSON-RPC interface at `url`."""
+    credentials = base64.b64encode(f"{user}:{password}".encode()).decode()
+
+    def call(method: str, params: List[Any]) -> Any:
+        request = urllib.request.Request(
+            url,
+            data=json.dumps(
+                {"jsonrpc": "1.0", "id": method, "method": method, "params": params},
+                default=float,
+            ).encode(),
+            headers={
+                "Authorization": f"Basic {credentials}",
+                "Content-Type": "application/json",
+            },
+        )
+        with urllib.request.urlopen(request) as response:
+            reply = json.loads(response.read(), parse_float=Decimal)
+        if reply.get("error"):
+            raise RuntimeError(reply["error"])
+        return reply["result"]
+
+    return call
+
+
+class Zcashd:
+    def __init__(self, call: Call) -> None:
+        self._call = call
+
+    def _request(self, method: str, params: List[Any]) -> Any:
+        # Optional params left out after the last one given.
+        while params and params[-1] is None:
+            params.pop()
+        return self._call(method, params)
+"#;
+
+fn client_method(command: &Command, method: &str, response: &str) -> String {
+    let names = param_names(&command.arguments);
+    // Without recorded arguments the params are left to the caller.
+    let (signature, values) = if command.metadata.is_null() {
+        (", *params: Any".to_string(), "list(params)".to_string())
+    } else {
+        let mut optional = false;
+        let signature = command
+            .arguments
+            .iter()
+            .zip(&names)
+            .map(|(argument, name)| {
+                // A param can't be required after one with a default.
+                optional |= !argument.required;
+                if optional {
+                    format!(
+                        ", {}: Optional[{}] = None",
+                        name,
+                        param_type(argument)
+                    )
+                } else {
+                    format!(", {}: {}", name, param_type(argument))
+                }
+            })
+            .collect::<String>();
+        (signature, format!("[{}]", names.join(", ")))
+    };
+    let mut doc = vec![command
+        .summary
+        .clone()
+        .unwrap_or_else(|| format!("`{}`", command.name))];
+    let params = command
+        .arguments
+        .iter()
+        .zip(&names)
+        .map(|(argument, name)| {
+            format!(
+                ":param {}: {}",
+                name,
+                argument.description.lines().next().unwrap_or("").trim()
+            )
+        })
+        .collect::<Vec<String>>();
+    if !params.is_empty() {
+        doc.push(String::new());
+        doc.extend(params);
+    }
+    if command.deprecated {
+        doc.push(String::new());
+        doc.push("Deprecated.".to_string());
+    }
+    let doc = doc
+        .iter()
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("        {}", line.replace("\"\"\"", "'''"))
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    format!(
+        "\n    def {method}(self{signature}) -> {types}.{response}:\n        \
+         \"\"\"\n{doc}\n        \"\"\"\n        \
+         return cast({types}.{response}, self._request(\"{command}\", {values}))\n",
+        method = method,
+        signature = signature,
+        types = TYPES_MODULE,
+        response = response,
+        doc = doc,
+        command = command.name,
+        values = values,
+    )
+}
+
+fn param_names(arguments: &[Argument]) -> Vec<String> {
+    let mut names: Vec<String> = vec![];
+    for argument in arguments {
+        let name = identifier(&argument.name);
+        let mut unique = name.clone();
+        let mut suffix = 1;
+        while names.contains(&unique) {
+            suffix += 1;
+            unique = format!("{}_{}", name, suffix);
+        }
+        names.push(unique);
+    }
+    names
+}
+
+fn param_type(argument: &Argument) -> String {
+    match argument.shape() {
+        Shape::Decimal => {
+            numeric_type(find_unit(&argument.description)).to_string()
+        }
+        Shape::String | Shape::Hexadecimal => "str".to_string(),
+        Shape::Bool => "bool".to_string(),
+        Shape::Array(_) => "List[Any]".to_string(),
+        _ => "Any".to_string(),
+    }
+}
+
+fn numeric_type(unit: Option<&str>) -> &'static str {
+    match unit {
+        Some("ZEC") => "Zec",
+        Some("zatoshi") => "Zatoshi",
+        Some("ZEC/kB") => "ZecPerKb",
+        _ => "Numeric",
+    }
+}
+
+// `hash|height` -> `hash_height`, escaped if it is a keyword.
+fn identifier(name: &str) -> String {
+    let identifier = field_name(name).trim_start_matches("r#").to_string();
+    if KEYWORDS.contains(&identifier.as_str()) {
+        format!("{}_", identifier)
+    } else {
+        identifier
+    }
+}
+
+#[derive(Default)]
+struct PythonTypes {
+    definitions: Vec<String>,
+    declared: HashSet<String>,
+}
+
+impl PythonTypes {
+    fn declare_response(&mut self, command: &Command) {
+        let name = format!("{}Response", type_name(&command.name));
+        let mut doc = vec![format!("The response of `{}`.", command.name)];
+        if let Some(summary) = &command.summary {
+            doc.push(String::new());
+            doc.extend(summary.lines().map(String::from));
+        }
+        match command.results.len() {
+            0 => self.declare_alias(&name, "Any", &doc),
+            1 => self.declare_named(&name, &command.results[0], &doc),
+            _ => {
+                let variants = (0..command.results.len())
+                    .map(|index| format!("{}{}", name, index))
+                    .collect::<Vec<String>>();
+                for (shape, variant) in command.results.iter().zip(&variants) {
+                    self.declare_named(variant, shape, &[]);
+                }
+                self.declare_alias(
+                    &name,
+                    &format!("Union[{}]", variants.join(", ")),
+                    &doc,
+                );
+            }
+        }
+    }
+
+    // Declares `name` as the type of `shape`: a `TypedDict` if it is an
+    // object, an alias otherwise.
+    fn declare_named(&mut self, name: &str, shape: &Shape, doc: &[String]) {
+        self.declared.insert(name.to_string());
+        if let Shape::Object(fields) = shape {
+            self.declare_typed_dict(name, fields, doc);
+        } else {
+            let python_type = self.python_type(shape, None, name);
+            self.declare_alias(name, &python_type, doc);
+        }
+    }
+
+    fn declare_alias(&mut self, name: &str, python_type: &str, doc: &[String]) {
+        self.declared.insert(name.to_string());
+        self.definitions.push(format!(
+            "\n\n{}{} = {}\n",
+            comment(doc, ""),
+            name,
+            python_type
+        ));
+    }
+
+    fn declare_typed_dict(
+        &mut self,
+        name: &str,
+        fields: &[Field],
+        doc: &[String],
+    ) {
+        self.declared.insert(name.to_string());
+        let members = fields
+            .iter()
+            .map(|field| {
+                let python_type = self.python_type(
+                    &field.shape,
+                    field.unit.as_deref(),
+                    &format!("{}{}", name, type_name(&field.name)),
+                );
+                let python_type = if field.optional {
+                    format!("NotRequired[{}]", python_type)
+                } else {
+                    python_type
+                };
+                (field, python_type)
+            })
+            .collect::<Vec<(&Field, String)>>();
+        // Keys that aren't identifiers need the functional syntax.
+        let class_syntax = fields.iter().all(|field| {
+            !KEYWORDS.contains(&field.name.as_str())
+                && field
+                    .name
+                    .starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && field
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+        let definition = if class_syntax {
+            let mut body = String::new();
+            for (field, python_type) in &members {
+                if let Some(description) = &field.description {
+                    body.push_str(&comment(
+                        &description
+                            .lines()
+                            .map(String::from)
+                            .collect::<Vec<String>>(),
+                        "    ",
+                    ));
+                }
+                body.push_str(&format!(
+                    "    {}: {}\n",
+                    field.name, python_type
+                ));
+            }
+            if body.is_empty() {
+                body.push_str("    pass\n");
+            }
+            format!("class {}(TypedDict):\n{}", name, body)
+        } else {
+            let mut body = String::new();
+            for (field, python_type) in &members {
+                body.push_str(&format!(
+                    "        {:?}: {},\n",
+                    field.name, python_type
+                ));
+            }
+            format!(
+                "{} = TypedDict(\n    \"{}\",\n    {{\n{}    }},\n)\n",
+                name, name, body
+            )
+        };
+        self.definitions.push(format!(
+            "\n\n{}{}",
+            comment(doc, ""),
+            definition
+        ));
+    }
+
+    // The Python type of `shape`, declaring any `TypedDict` it needs as
+    // `name`.
+    fn python_type(
+        &mut self,
+        shape: &Shape,
+        unit: Option<&str>,
+        name: &str,
+    ) -> String {
+        match shape {
+            Shape::Decimal => numeric_type(unit).to_string(),
+            Shape::String | Shape::Hexadecimal => "str".to_string(),
+            Shape::Bool => "bool".to_string(),
+            Shape::Insufficient => "Any".to_string(),
+            Shape::Enum(values) => format!(
+                "Literal[{}]",
+                values
+                    .iter()
+                    .map(|value| format!("{:?}", value))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Shape::Array(item) => format!(
+                "List[{}]",
+                self.python_type(item, unit, &format!("{}Item", name))
+            ),
+            Shape::Map(_, value) => format!(
+                "Dict[str, {}]",
+                self.python_type(value, unit, &format!("{}Entry", name))
+            ),
+            Shape::Object(fields) => {
+                let name = self.unique(name);
+                self.declare_typed_dict(&name, fields, &[]);
+                name
+            }
+        }
+    }
+
+    fn unique(&self, name: &str) -> String {
+        let mut unique = name.to_string();
+        let mut suffix = 1;
+        while self.declared.contains(&unique) {
+            suffix += 1;
+            unique = format!("{}{}", name, suffix);
+        }
+        unique
+    }
+}
+
+fn comment(lines: &[String], indent: &str) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            if line.is_empty() {
+                format!("{}#:\n", indent)
+            } else {
+                format!("{}#: {}\n", indent, line.trim())
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn python_types_amounts_and_optional_fields() {
+        let command = Command::from_json(
+            "getinfo",
+            &json!([{"balance": "Decimal", "Option<proxy>": "String"}]),
+            json!({
+                "fields": [{
+                    "/balance": {
+                        "description": "the total balance in ZEC",
+                        "unit": "ZEC"
+                    }
+                }]
+            }),
+        );
+        let types = python_types(&[command], "test");
+        assert!(types.contains(
+            "#: The response of `getinfo`.\n\
+             class GetinfoResponse(TypedDict):\n    \
+             proxy: NotRequired[str]\n    \
+             #: the total balance in ZEC\n    \
+             balance: Zec\n"
+        ));
+    }
+
+    #[test]
+    fn python_client_z_sendmany() {
+        let command = Command::from_json(
+            "z_sendmany",
+            &json!(["String"]),
+            json!({
+                "description": {"summary": "Send multiple times."},
+                "arguments": [
+                    {"name": "fromaddress", "type": "string",
+                     "required": true, "description": "The taddr or zaddr"},
+                    {"name": "fee", "type": "numeric", "required": false,
+                     "description": "The fee amount in ZEC to attach"}
+                ]
+            }),
+        );
+        let client = python_client(&[command], "test");
+        assert!(client.contains(
+            "    def z_sendmany(self, fromaddress: str, \
+             fee: Optional[Zec] = None) -> zcashd_types.ZSendmanyResponse:\n"
+        ));
+        assert!(client.contains(
+            "        return cast(zcashd_types.ZSendmanyResponse, \
+             self._request(\"z_sendmany\", [fromaddress, fee]))\n"
+        ));
+    }
+}
@@ -0,0 +1,471 @@
+//! An API reference of the interpreted commands, rendered to Markdown or
+//! HTML from the same pages.
+use crate::utils::model::{Command, Field, Shape};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Markdown => "md",
+            Format::Html => "html",
+        }
+    }
+}
+
+// Text in blocks may contain `code spans`.
+enum Block {
+    Heading(usize, String),
+    Paragraph(String),
+    Badges(Vec<String>),
+    Table(Vec<&'static str>, Vec<Vec<String>>),
+    Code(String),
+    /// Links to other pages of the reference, with what they are about.
+    Links(Vec<(String, String)>),
+}
+
+/// The reference page of `command`, with its category, description,
+/// arguments, every result variant as a table of its (nested) fields, and
+/// its examples.
+pub fn command_page(command: &Command, format: Format) -> String {
+    let mut blocks = vec![Block::Heading(1, command.name.clone())];
+    let mut badges = vec![];
+    if let Some(category) = &command.category {
+        badges.push(category.clone());
+    }
+    if command.deprecated {
+        badges.push("deprecated".to_string());
+    }
+    if command.experimental {
+        badges.push("experimental".to_string());
+    }
+    if !badges.is_empty() {
+        blocks.push(Block::Badges(badges));
+    }
+    let description = &command.metadata["description"];
+    if let Some(usage) = description["usage"].as_str() {
+        blocks.push(Block::Code(usage.to_string()));
+    }
+    if let Some(summary) = &command.summary {
+        blocks.push(Block::Paragraph(summary.clone()));
+    }
+    for key in ["body", "notes"] {
+        for paragraph in description[key].as_array().into_iter().flatten() {
+            if let Some(paragraph) = paragraph.as_str() {
+                blocks.push(Block::Paragraph(paragraph.to_string()));
+            }
+        }
+    }
+    for warning in description["warnings"].as_array().into_iter().flatten() {
+        if let Some(text) = warning["text"].as_str() {
+            blocks.push(Block::Paragraph(format!("**Warning:** {}", text)));
+        }
+    }
+
+    blocks.push(Block::Heading(2, "Arguments".to_string()));
+    if command.metadata.is_null() {
+        blocks.push(Block::Paragraph("Not recorded.".to_string()));
+    } else if command.arguments.is_empty() {
+        blocks.push(Block::Paragraph("None.".to_string()));
+    } else {
+        let rows = command
+            .arguments
+            .iter()
+            .enumerate()
+            .map(|(index, argument)| {
+                vec![
+                    (index + 1).to_string(),
+                    format!("`{}`", argument.name),
+                    argument.kind.clone(),
+                    if argument.required { "yes" } else { "no" }.to_string(),
+                    argument
+                        .default
+                        .as_ref()
+                        .map_or(String::new(), |d| format!("`{}`", d)),
+                    argument.description.replace('\n', " "),
+                ]
+            })
+            .collect();
+        blocks.push(Block::Table(
+            vec!["#", "Name", "Type", "Required", "Default", "Description"],
+            rows,
+        ));
+    }
+
+    if command.results.is_empty() {
+        blocks.push(Block::Heading(2, "Result".to_string()));
+        blocks.push(Block::Paragraph("None documented.".to_string()));
+    }
+    for (index, shape) in command.results.iter().enumerate() {
+        let heading =
+            match (command.results.len(), command.result_conditions.get(index))
+            {
+                (1, _) => "Result".to_string(),
+                (_, Some(Some(condition))) => format!("Result ({})", condition),
+                _ => format!("Result {}", index + 1),
+            };
+        blocks.push(Block::Heading(2, heading));
+        let mut rows = vec![];
+        match shape {
+            Shape::Object(fields) => field_rows(fields, "", &mut rows),
+            shape => {
+                rows.push(vec![
+                    "(result)".to_string(),
+                    type_description(shape),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                ]);
+                if let Some(fields) = nested_fields(shape) {
+                    field_rows(fields, &item_path("", shape), &mut rows);
+                }
+            }
+        }
+        blocks.push(Block::Table(
+            vec!["Field", "Type", "Optional", "Unit", "Description"],
+            rows,
+        ));
+    }
+
+    if !command.examples.is_empty() {
+        blocks.push(Block::Heading(2, "Examples".to_string()));
+        blocks.push(Block::Code(
+            command
+                .examples
+                .iter()
+                .map(|example| example.line.as_str())
+                .collect::<Vec<&str>>()
+                .join("\n"),
+        ));
+    }
+    render(&command.name, &blocks, format)
+}
+
+/// The index of the reference, linking every command in `commands` by
+/// category.
+pub fn index_page(
+    commands: &[Command],
+    zcashd_version: &str,
+    format: Format,
+) -> String {
+    let mut categories: BTreeMap<&str, Vec<&Command>> = BTreeMap::new();
+    for command in commands {
+        categories
+            .entry(command.category.as_deref().unwrap_or("Uncategorized"))
+            .or_default()
+            .push(command);
+    }
+    let title = format!("zcashd {} RPC reference", zcashd_version);
+    let mut blocks = vec![Block::Heading(1, title.clone())];
+    for (category, commands) in categories {
+        blocks.push(Block::Heading(2, category.to_string()));
+        blocks.push(Block::Links(
+            commands
+                .iter()
+                .map(|command| {
+                    let mut about = command.summary.clone().unwrap_or_default();
+                    if command.deprecated {
+                        about.insert_str(0, "(deprecated) ");
+                    }
+                    if command.experimental {
+                        about.insert_str(0, "(experimental) ");
+                    }
+                    (command.name.clone(), about)
+                })
+                .collect(),
+        ));
+    }
+    render(&title, &blocks, format)
+}
+
+// Rows of `fields` and of the fields nested in them, named by their path
+// from the result, e.g. `tx[].vin[].txid`.
+fn field_rows(fields: &[Field], path: &str, rows: &mut Vec<Vec<String>>) {
+    for field in fields {
+        let field_path = if path.is_empty() {
+            field.name.clone()
+        } else {
+            format!("{}.{}", path, field.name)
+        };
+        rows.push(vec![
+            format!("`{}`", field_path),
+            type_description(&field.shape),
+            if field.optional { "yes" } else { "" }.to_string(),
+            field.unit.clone().unwrap_or_default(),
+            field
+                .description
+                .clone()
+                .unwrap_or_default()
+                .replace('\n', " "),
+        ]);
+        if let Some(fields) = nested_fields(&field.shape) {
+            field_rows(fields, &item_path(&field_path, &field.shape), rows);
+        }
+    }
+}
+
+// The fields of the objects `shape` holds, if it holds any.
+fn nested_fields(shape: &Shape) -> Option<&[Field]> {
+    match shape {
+        Shape::Object(fields) => Some(fields),
+        Shape::Array(item) | Shape::Map(_, item) => nested_fields(item),
+        _ => None,
+    }
+}
+
+// `tx` -> `tx[]`, `transactionid` map -> `<transactionid>`
+fn item_path(path: &str, shape: &Shape) -> String {
+    match shape {
+        Shape::Array(item) => item_path(&format!("{}[]", path), item),
+        Shape::Map(key, value) => {
+            let entry = if path.is_empty() {
+                format!("<{}>", key)
+            } else {
+                format!("{}.<{}>", path, key)
+            };
+            item_path(&entry, value)
+        }
+        _ => path.to_string(),
+    }
+}
+
+fn type_description(shape: &Shape) -> String {
+    match shape {
+        Shape::Decimal => "numeric".to_string(),
+        Shape::String => "string".to_string(),
+        Shape::Bool => "boolean".to_string(),
+        Shape::Hexadecimal => "hexadecimal string".to_string(),
+        Shape::Insufficient => "undocumented".to_string(),
+        Shape::Enum(values) => format!(
+            "one of {}",
+            values
+                .iter()
+                .map(|value| format!("`{}`", value))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        Shape::Array(item) => format!("array of {}", type_description(item)),
+        Shape::Map(key, value) => {
+            format!("object of {} by {}", type_description(value), key)
+        }
+        Shape::Object(_) => "object".to_string(),
+    }
+}
+
+fn render(title: &str, blocks: &[Block], format: Format) -> String {
+    let mut page = String::new();
+    match format {
+        Format::Markdown => {
+            for block in blocks {
+                page.push_str(&markdown(block));
+                page.push('\n');
+            }
+        }
+        Format::Html => {
+            page.push_str(&format!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+                 <title>{}</title>\n<style>\n\
+                 table {{ border-collapse: collapse; }}\n\
+                 td, th {{ border: 1px solid #ccc; padding: 0.25em 0.5em; }}\n\
+                 .badge {{ border-radius: 0.5em; background: #eee; \
+                 padding: 0 0.5em; margin-right: 0.5em; }}\n\
+                 </style>\n</head>\n<body>\n",
+                escape_html(title)
+            ));
+            for block in blocks {
+                page.push_str(&html(block));
+            }
+            page.push_str("</body>\n</html>\n");
+        }
+    }
+    page
+}
+
+fn markdown(block: &Block) -> String {
+    match block {
+        Block::Heading(level, text) => {
+            format!("{} {}\n", "#".repeat(*level), text)
+        }
+        Block::Paragraph(text) => format!("{}\n", text),
+        Block::Badges(badges) => format!(
+            "{}\n",
+            badges
+                .iter()
+                .map(|badge| format!("`{}`", badge))
+                .collect::<Vec<String>>()
+                .join(" ")
+        ),
+        Block::Table(header, rows) => {
+            let mut table = format!(
+                "| {} |\n|{}\n",
+                header.join(" | "),
+                "---|".repeat(header.len())
+            );
+            for row in rows {
+                table.push_str(&format!(
+                    "| {} |\n",
+                    row.iter()
+                        .map(|cell| cell.replace('|', "\\|"))
+                        .collect::<Vec<String>>()
+                        .join(" | ")
+                ));
+            }
+            table
+        }
+        Block::Code(code) => format!("```\n{}\n```\n", code),
+        Block::Links(links) => links
+            .iter()
+            .map(|(name, about)| {
+                let link = format!("- [`{}`]({}.md)", name, name);
+                if about.is_empty() {
+                    format!("{}\n", link)
+                } else {
+                    format!("{}: {}\n", link, about)
+                }
+            })
+            .collect(),
+    }
+}
+
+fn html(block: &Block) -> String {
+    match block {
+        Block::Heading(level, text) => {
+            format!("<h{}>{}</h{}>\n", level, inline_html(text), level)
+        }
+        Block::Paragraph(text) => format!("<p>{}</p>\n", inline_html(text)),
+        Block::Badges(badges) => format!(
+            "<p>{}</p>\n",
+            badges
+                .iter()
+                .map(|badge| format!(
+                    "<span class=\"badge\">{}</span>",
+                    escape_html(badge)
+                ))
+                .collect::<String>()
+        ),
+        Block::Table(header, rows) => {
+            let mut table = "<table>\n<tr>".to_string();
+            for name in header {
+                table.push_str(&format!("<th>{}</th>", escape_html(name)));
+            }
+            table.push_str("</tr>\n");
+            for row in rows {
+                table.push_str("<tr>");
+                for cell in row {
+                    table.push_str(&format!("<td>{}</td>", inline_html(cell)));
+                }
+                table.push_str("</tr>\n");
+            }
+            table.push_str("</table>\n");
+            table
+        }
+        Block::Code(code) => {
+            format!("<pre><code>{}</code></pre>\n", escape_html(code))
+        }
+        Block::Links(links) => {
+            let mut list = "<ul>\n".to_string();
+            for (name, about) in links {
+                list.push_str(&format!(
+                    "<li><a href=\"{}.html\"><code>{}</code></a>",
+                    escape_html(name),
+                    escape_html(name)
+                ));
+                if !about.is_empty() {
+                    list.push_str(&format!(": {}", escape_html(about)));
+                }
+                list.push_str("</li>\n");
+            }
+            list.push_str("</ul>\n");
+            list
+        }
+    }
+}
+
+// Escapes `text`, turning its `code spans` and `**strong**` text into tags.
+fn inline_html(text: &str) -> String {
+    let mut html = String::new();
+    for (index, part) in escape_html(text).split('`').enumerate() {
+        if index % 2 == 1 {
+            html.push_str(&format!("<code>{}</code>", part));
+        } else {
+            for (index, part) in part.split("**").enumerate() {
+                if index % 2 == 1 {
+                    html.push_str(&format!("<strong>{}</strong>", part));
+                } else {
+                    html.push_str(part);
+                }
+            }
+        }
+    }
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use serde_json::json;
+
+    fn getrawmempool() -> Command {
+        Command::from_json(
+            "getrawmempool",
+            &json!([
+                ["String"],
+                {"transactionid": {"size": "Decimal", "Option<depends>": ["String"]}}
+            ]),
+            json!({
+                "category": "Blockchain",
+                "description": {"usage": "getrawmempool ( verbose )",
+                                "summary": "Returns all transaction ids."},
+                "capabilities": {"deprecated": true},
+                "arguments": [
+                    {"name": "verbose", "type": "boolean", "required": false,
+                     "default": "false", "description": "True for a json object"}
+                ],
+                "fields": [{}, {"/transactionid/size": {
+                    "description": "transaction size in bytes",
+                    "unit": "bytes"
+                }}],
+                "result_conditions": ["verbose = false", "verbose = true"]
+            }),
+        )
+    }
+
+    #[test]
+    fn command_page_markdown_getrawmempool() {
+        let page = command_page(&getrawmempool(), Format::Markdown);
+        assert!(
+            page.starts_with("# getrawmempool\n\n`Blockchain` `deprecated`\n")
+        );
+        assert!(page.contains(
+            "| 1 | `verbose` | boolean | no | `false` | True for a json object |\n"
+        ));
+        assert!(page.contains("## Result (verbose = true)\n"));
+        assert!(page.contains(
+            "| (result) | object of object by transactionid |  |  |  |\n\
+             | `<transactionid>.depends` | array of string | yes |  |  |\n\
+             | `<transactionid>.size` | numeric |  | bytes | transaction size in bytes |\n"
+        ));
+    }
+
+    #[test]
+    fn index_page_html_links_by_category() {
+        let page = index_page(&[getrawmempool()], "v4.3.0", Format::Html);
+        assert!(page.contains("<h2>Blockchain</h2>\n<ul>\n"));
+        assert!(page.contains(
+            "<li><a href=\"getrawmempool.html\"><code>getrawmempool</code></a>: \
+             (deprecated) Returns all transaction ids.</li>\n"
+        ));
+    }
+}
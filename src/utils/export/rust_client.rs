@@ -0,0 +1,371 @@
+use crate::utils::export::rust_types::{field_name, response_names};
+use crate::utils::fields::find_unit;
+use crate::utils::model::{Argument, Command, Shape};
+use serde_json::Value;
+
+// Everything the generated methods share.  `super::responses` is the module
+// `rust_types::rust_module` generates, which the client sits next to.
+const CLIENT_PRELUDE: &str = r#"
+/// Carries JSON-RPC requests to zcashd and back, e.g. over HTTP, or answers
+/// them with canned responses (see `MockTransport`).
+pub trait Transport {
+    type Error;
+
+    /// The `result` of calling `method` with positional `params`.
+    fn call(&mut self, method: &str, params: Vec<Value>)
+        -> Result<Value, Self::Error>;
+}
+
+#[derive(Debug)]
+pub enum ClientError<E> {
+    Transport(E),
+    /// The result isn't the documented response.
+    Response(serde_json::Error),
+}
+
+pub struct Client<T: Transport> {
+    transport: T,
+}
+
+impl<T: Transport> Client<T> {
+    pub fn new(transport: T) -> Client<T> {
+        Client { transport }
+    }
+
+    pub fn transport(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    fn request<R: DeserializeOwned>(
+        &mut self,
+        method: &str,
+        mut params: Vec<Value>,
+    ) -> Result<R, ClientError<T::Error>> {
+        // Optional params left out after the last one given.
+        while params.last() == Some(&Value::Null) {
+            params.pop();
+        }
+        let result = self
+            .transport
+            .call(method, params)
+            .map_err(ClientError::Transport)?;
+        serde_json::from_value(result).map_err(ClientError::Response)
+    }
+"#;
+
+const MOCK_TRANSPORT: &str = r#"
+/// Answers each method with the result recorded for it, and records the
+/// calls made.
+#[derive(Default)]
+pub struct MockTransport {
+    pub results: HashMap<String, Value>,
+    pub calls: Vec<(String, Vec<Value>)>,
+}
+
+impl Transport for MockTransport {
+    type Error = String;
+
+    fn call(
+        &mut self,
+        method: &str,
+        params: Vec<Value>,
+    ) -> Result<Value, String> {
+        self.calls.push((method.to_string(), params));
+        self.results
+            .get(method)
+            .cloned()
+            .ok_or_else(|| format!("no result for {}", method))
+    }
+}
+"#;
+
+/// A Rust module with a `Client` method per command in `commands`, taking
+/// its arguments as positional params, over a pluggable `Transport`.
+/// Commands with several result variants also get a method per variant,
+/// named for what the variant is documented for, e.g. `getblock_verbosity_1`,
+/// or else its index, e.g. `getblock_1`.
+pub fn rust_client(commands: &[Command], source: &str) -> String {
+    let mut methods = String::new();
+    for command in commands {
+        let (response, variants) = response_names(command);
+        methods.push_str(&client_method(
+            command,
+            &field_name(&command.name),
+            &response,
+            None,
+        ));
+        if variants.len() > 1 {
+            for (index, variant) in variants.iter().enumerate() {
+                methods.push_str(&client_method(
+                    command,
+                    &variant_method_name(command, index),
+                    variant,
+                    command
+                        .result_conditions
+                        .get(index)
+                        .and_then(|condition| condition.as_deref()),
+                ));
+            }
+        }
+    }
+    // Methods whose params are all left to the caller build none.
+    let json = if methods.contains("json!(") {
+        "{json, Value}"
+    } else {
+        "Value"
+    };
+    let mut module = format!(
+        "//! zcashd RPC client generated by quizface from `{}`.\n\
+         //! Do not edit.\n\
+         #![allow(clippy::too_many_arguments, deprecated)]\n\
+         use super::responses::*;\n\
+         use serde::de::DeserializeOwned;\n\
+         use serde_json::{};\n\
+         use std::collections::HashMap;\n{}",
+        source, json, CLIENT_PRELUDE
+    );
+    module.push_str(&methods);
+    module.push_str("}\n");
+    module.push_str(MOCK_TRANSPORT);
+    module
+}
+
+/// `getblock` result variant 1 -> `getblock_verbosity_1`
+pub fn variant_method_name(command: &Command, index: usize) -> String {
+    match command.result_conditions.get(index) {
+        Some(Some(condition)) => {
+            format!("{}_{}", command.name, field_name(condition))
+        }
+        _ => format!("{}_{}", command.name, index),
+    }
+}
+
+// `condition`, e.g. `verbosity = 1`, fixes the argument it names to the
+// value that selects the variant, leaving it out of the signature.
+fn client_method(
+    command: &Command,
+    method: &str,
+    response: &str,
+    condition: Option<&str>,
+) -> String {
+    let params = param_names(&command.arguments);
+    let selected =
+        condition.and_then(|condition| selected_param(condition, &params));
+    let mut definition = String::new();
+    match &command.summary {
+        Some(summary) => {
+            for line in summary.lines() {
+                definition.push_str(&format!("\n    /// {}", line.trim()));
+            }
+        }
+        None => definition.push_str(&format!("\n    /// `{}`", command.name)),
+    }
+    definition.push('\n');
+    if !command.arguments.is_empty() {
+        definition.push_str("    ///\n");
+    }
+    for (argument, param) in command.arguments.iter().zip(&params) {
+        if let Some((selected, value)) = &selected {
+            if selected == param {
+                definition.push_str(&format!(
+                    "    /// * `{}`: {}, which selects this result\n",
+                    param.trim_start_matches("r#"),
+                    value
+                ));
+                continue;
+            }
+        }
+        definition.push_str(&format!(
+            "    /// * `{}`: {}\n",
+            param.trim_start_matches("r#"),
+            argument.description.lines().next().unwrap_or("").trim()
+        ));
+    }
+    if command.deprecated {
+        definition.push_str("    #[deprecated]\n");
+    }
+    // Without recorded arguments the params are left to the caller.
+    let (signature, values) = if command.metadata.is_null() {
+        (", params: Vec<Value>".to_string(), "params".to_string())
+    } else {
+        (
+            command
+                .arguments
+                .iter()
+                .zip(&params)
+                .filter(|(_, param)| {
+                    selected
+                        .as_ref()
+                        .is_none_or(|(selected, _)| selected != *param)
+                })
+                .map(|(argument, param)| {
+                    format!(", {}: {}", param, param_type(argument))
+                })
+                .collect::<String>(),
+            format!(
+                "vec![{}]",
+                params
+                    .iter()
+                    .map(|param| match &selected {
+                        Some((selected, value)) if selected == param => {
+                            format!("json!({})", value)
+                        }
+                        _ => format!("json!({})", param),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        )
+    };
+    definition.push_str(&format!(
+        "    pub fn {}(&mut self{}) -> Result<{}, ClientError<T::Error>> {{\n\
+         \x20       self.request(\"{}\", {})\n    }}\n",
+        method, signature, response, command.name, values
+    ));
+    definition
+}
+
+// Argument names as Rust identifiers, told apart if they clash.
+fn param_names(arguments: &[Argument]) -> Vec<String> {
+    let mut names: Vec<String> = vec![];
+    for argument in arguments {
+        let name = field_name(&argument.name);
+        let mut unique = name.clone();
+        let mut suffix = 1;
+        while names.contains(&unique) {
+            suffix += 1;
+            unique = format!("{}_{}", name, suffix);
+        }
+        names.push(unique);
+    }
+    names
+}
+
+// The param a `name = value` condition names, and its value as a Rust
+// expression `json!` takes, e.g. `("verbosity", "1")`.
+fn selected_param(
+    condition: &str,
+    params: &[String],
+) -> Option<(String, String)> {
+    let (name, value) = condition.split_once('=')?;
+    let param = field_name(name.trim());
+    if !params.contains(&param) {
+        return None;
+    }
+    let value = value.trim();
+    let value = match serde_json::from_str::<Value>(value) {
+        Ok(Value::Number(number)) => number.to_string(),
+        Ok(Value::Bool(flag)) => flag.to_string(),
+        _ => format!("{:?}", value.trim_matches('"')),
+    };
+    Some((param, value))
+}
+
+fn param_type(argument: &Argument) -> String {
+    let rust_type = match argument.shape() {
+        Shape::Decimal if !is_amount(argument) => "i64".to_string(),
+        shape => shape_type(&shape),
+    };
+    if argument.required {
+        rust_type
+    } else {
+        format!("Option<{}>", rust_type)
+    }
+}
+
+// zcashd reads amounts with `AmountFromValue`, which takes fractions, and
+// other numbers with `get_int`, which rejects `1.0`.
+fn is_amount(argument: &Argument) -> bool {
+    match find_unit(&argument.description) {
+        Some("ZEC") | Some("ZEC/kB") => true,
+        Some(_) => false,
+        None => argument.description.to_lowercase().contains("amount"),
+    }
+}
+
+fn shape_type(shape: &Shape) -> String {
+    match shape {
+        Shape::Decimal => "f64".to_string(),
+        Shape::String | Shape::Hexadecimal => "String".to_string(),
+        Shape::Bool => "bool".to_string(),
+        Shape::Array(item) => format!("Vec<{}>", shape_type(item)),
+        _ => "Value".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn rust_client_getblock_variants() {
+        let command = Command::from_json(
+            "getblock",
+            &json!(["hexadecimal", {"hash": "hexadecimal"}]),
+            json!({
+                "description": {"summary": "Returns the block."},
+                "arguments": [
+                    {"name": "hash|height", "type": "string",
+                     "required": true, "description": "The block hash"},
+                    {"name": "verbosity", "type": "numeric",
+                     "required": false, "description": "0 or 1"}
+                ],
+                "result_conditions": ["verbosity = 0", "verbosity = 1"]
+            }),
+        );
+        let client = rust_client(&[command], "test");
+        assert!(client.contains("use serde_json::{json, Value};\n"));
+        assert!(client.contains(
+            "    /// Returns the block.\n    ///\n    \
+             /// * `hash_height`: The block hash\n    \
+             /// * `verbosity`: 0 or 1\n    \
+             pub fn getblock(&mut self, hash_height: String, \
+             verbosity: Option<i64>) -> \
+             Result<GetblockResponse, ClientError<T::Error>> {\n        \
+             self.request(\"getblock\", \
+             vec![json!(hash_height), json!(verbosity)])\n    }\n"
+        ));
+        assert!(client.contains(
+            "    /// * `verbosity`: 1, which selects this result\n    \
+             pub fn getblock_verbosity_1(&mut self, hash_height: String) -> \
+             Result<GetblockResponse1, ClientError<T::Error>> {\n        \
+             self.request(\"getblock\", \
+             vec![json!(hash_height), json!(1)])\n    }\n"
+        ));
+    }
+
+    #[test]
+    fn rust_client_amounts_and_integers() {
+        let command = Command::from_json(
+            "z_sendmany",
+            &json!(["String"]),
+            json!({
+                "arguments": [
+                    {"name": "minconf", "type": "numeric", "required": false,
+                     "description": "Only use funds confirmed at least \
+                                     this many times."},
+                    {"name": "fee", "type": "numeric", "required": false,
+                     "description": "The fee amount to attach to this \
+                                     transaction."},
+                    {"name": "fee_delta", "type": "numeric", "required": true,
+                     "description": "The fee value (in satoshis) to add"}
+                ]
+            }),
+        );
+        let client = rust_client(&[command], "test");
+        assert!(client.contains(
+            "pub fn z_sendmany(&mut self, minconf: Option<i64>, \
+             fee: Option<f64>, fee_delta: i64)"
+        ));
+    }
+
+    #[test]
+    fn rust_client_without_metadata_imports_no_json() {
+        let command =
+            Command::from_json("getinfo", &json!(["String"]), Value::Null);
+        let client = rust_client(&[command], "test");
+        assert!(client.contains("use serde_json::Value;\n"));
+        assert!(!client.contains("json!("));
+    }
+}
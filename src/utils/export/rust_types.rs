@@ -0,0 +1,297 @@
+use crate::utils::model::{Command, Field, Shape};
+use std::collections::HashSet;
+
+// Words that can't be field names as they are.
+const KEYWORDS: [&str; 41] = [
+    "as", "async", "await", "box", "break", "const", "continue", "crate",
+    "dyn", "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in",
+    "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+    "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "yield", "try",
+];
+// Keywords that can't be raw identifiers either.
+const NOT_RAW: [&str; 4] = ["crate", "self", "Self", "super"];
+
+/// A Rust module declaring a serde type for the response of every command
+/// in `commands`, named `<Command>Response`.  Nested objects get types named
+/// from their path, e.g. `GetblockchaininfoResponseSoftforksItemEnforce`, and
+/// commands with several result variants an untagged enum of them.
+pub fn rust_module(commands: &[Command], source: &str) -> String {
+    let mut types = RustTypes::default();
+    for command in commands {
+        types.declare_response(command);
+    }
+    format!(
+        "//! zcashd RPC response types generated by quizface from `{}`.\n\
+         //! Do not edit.\n\
+         #![allow(clippy::large_enum_variant)]\n\
+         use serde::{{Deserialize, Serialize}};\n\
+         use std::collections::HashMap;\n{}",
+        source,
+        types.definitions.concat()
+    )
+}
+
+/// `z_getbalance` -> `ZGetbalance`
+pub fn type_name(words: &str) -> String {
+    words
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            format!("{}{}", first, chars.as_str())
+        })
+        .collect()
+}
+
+/// `scriptPubKey` -> `script_pub_key`, escaped if it is a keyword, or
+/// suffixed with `_` if it is one that can't be escaped.
+pub fn field_name(key: &str) -> String {
+    let mut name = String::new();
+    for (i, c) in key.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 && !name.ends_with('_') {
+                name.push('_');
+            }
+            name.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            name.push(c);
+        } else if !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    if name.starts_with(|c: char| c.is_ascii_digit()) || name.is_empty() {
+        name.insert(0, '_');
+    }
+    if NOT_RAW.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{}", name)
+    } else {
+        name
+    }
+}
+
+/// The response type `rust_module` declares for `command`, and those of
+/// its result variants.
+pub fn response_names(command: &Command) -> (String, Vec<String>) {
+    let name = format!("{}Response", type_name(&command.name));
+    let variants = (0..command.results.len())
+        .map(|index| format!("{}{}", name, index))
+        .collect();
+    (name, variants)
+}
+
+#[derive(Default)]
+struct RustTypes {
+    definitions: Vec<String>,
+    declared: HashSet<String>,
+}
+
+impl RustTypes {
+    fn declare_response(&mut self, command: &Command) {
+        let (name, variants) = response_names(command);
+        let mut doc = format!("/// The response of `{}`.\n", command.name);
+        if let Some(summary) = &command.summary {
+            doc.push_str(&format!("///\n{}", doc_comment(summary, "")));
+        }
+        if command.deprecated {
+            doc.push_str("#[deprecated]\n");
+        }
+        match command.results.len() {
+            0 => self.definitions.push(format!(
+                "\n{}pub type {} = serde_json::Value;\n",
+                doc, name
+            )),
+            1 => self.declare_named(&name, &command.results[0], &doc),
+            _ => {
+                let mut definition = format!(
+                    "\n{}#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]\n\
+                     #[serde(untagged)]\npub enum {} {{\n",
+                    doc, name
+                );
+                for (index, (shape, variant)) in
+                    command.results.iter().zip(&variants).enumerate()
+                {
+                    self.declare_named(variant, shape, "");
+                    definition
+                        .push_str(&format!("    V{}({}),\n", index, variant));
+                }
+                definition.push_str("}\n");
+                self.definitions.push(definition);
+            }
+        }
+    }
+
+    // Declares `name` as the type of `shape`: a struct or enum if it is an
+    // object or enumeration, an alias otherwise.
+    fn declare_named(&mut self, name: &str, shape: &Shape, doc: &str) {
+        match shape {
+            Shape::Object(fields) => self.declare_struct(name, fields, doc),
+            Shape::Enum(values) => self.declare_enum(name, values, doc),
+            shape => {
+                let rust_type = self.rust_type(shape, name);
+                self.declared.insert(name.to_string());
+                self.definitions.push(format!(
+                    "\n{}pub type {} = {};\n",
+                    doc, name, rust_type
+                ));
+            }
+        }
+    }
+
+    fn declare_struct(&mut self, name: &str, fields: &[Field], doc: &str) {
+        self.declared.insert(name.to_string());
+        let mut members = String::new();
+        let mut names: Vec<String> = vec![];
+        for field in fields {
+            // `Self` and `self` are both `self_`, say.
+            let mut member = field_name(&field.name);
+            let mut suffix = 1;
+            while names.contains(&member) {
+                suffix += 1;
+                member = format!(
+                    "{}_{}",
+                    field_name(&field.name)
+                        .trim_start_matches("r#")
+                        .trim_end_matches('_'),
+                    suffix
+                );
+            }
+            names.push(member.clone());
+            let rust_type = self.rust_type(
+                &field.shape,
+                &format!("{}{}", name, type_name(&field.name)),
+            );
+            if let Some(description) = &field.description {
+                members.push_str(&doc_comment(description, "    "));
+            }
+            if member.trim_start_matches("r#") != field.name {
+                members.push_str(&format!(
+                    "    #[serde(rename = \"{}\")]\n",
+                    field.name.escape_default()
+                ));
+            }
+            if field.optional {
+                members.push_str(
+                    "    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n",
+                );
+                members.push_str(&format!(
+                    "    pub {}: Option<{}>,\n",
+                    member, rust_type
+                ));
+            } else {
+                members
+                    .push_str(&format!("    pub {}: {},\n", member, rust_type));
+            }
+        }
+        self.definitions.push(format!(
+            "\n{}#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]\n\
+             pub struct {} {{\n{}}}\n",
+            doc, name, members
+        ));
+    }
+
+    // The Rust type of `shape`, declaring any type it needs as `name`.
+    fn rust_type(&mut self, shape: &Shape, name: &str) -> String {
+        match shape {
+            Shape::Decimal => "f64".to_string(),
+            Shape::String | Shape::Hexadecimal => "String".to_string(),
+            Shape::Bool => "bool".to_string(),
+            Shape::Insufficient => "serde_json::Value".to_string(),
+            Shape::Enum(values) => {
+                let name = self.unique(name);
+                self.declare_enum(&name, values, "");
+                name
+            }
+            Shape::Array(item) => format!(
+                "Vec<{}>",
+                self.rust_type(item, &format!("{}Item", name))
+            ),
+            Shape::Map(_, value) => format!(
+                "HashMap<String, {}>",
+                self.rust_type(value, &format!("{}Entry", name))
+            ),
+            Shape::Object(fields) => {
+                let name = self.unique(name);
+                self.declare_struct(&name, fields, "");
+                name
+            }
+        }
+    }
+
+    fn declare_enum(&mut self, name: &str, values: &[String], doc: &str) {
+        self.declared.insert(name.to_string());
+        let mut definition = format!(
+            "\n{}#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]\n\
+             pub enum {} {{\n",
+            doc, name
+        );
+        for value in values {
+            definition.push_str(&format!(
+                "    #[serde(rename = \"{}\")]\n    {},\n",
+                value.escape_default(),
+                type_name(value)
+            ));
+        }
+        definition.push_str("}\n");
+        self.definitions.push(definition);
+    }
+
+    fn unique(&self, name: &str) -> String {
+        let mut unique = name.to_string();
+        let mut suffix = 1;
+        while self.declared.contains(&unique) {
+            suffix += 1;
+            unique = format!("{}{}", name, suffix);
+        }
+        unique
+    }
+}
+
+fn doc_comment(text: &str, indent: &str) -> String {
+    text.lines()
+        .map(|line| format!("{}/// {}\n", indent, line.trim()))
+        .collect()
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn rust_module_getblock_variants() {
+        let command = Command::from_json(
+            "getblock",
+            &json!(["String", {"Option<type>": "String", "tx": ["String"]}]),
+            json!({"fields": [{}, {"/tx": {"description": "the txids"}}]}),
+        );
+        let module = rust_module(&[command], "test");
+        assert!(module.contains(
+            "#[serde(untagged)]\npub enum GetblockResponse {\n    \
+             V0(GetblockResponse0),\n    V1(GetblockResponse1),\n}\n"
+        ));
+        assert!(module.contains("pub type GetblockResponse0 = String;\n"));
+        assert!(module.contains(
+            "pub struct GetblockResponse1 {\n    \
+             #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    \
+             pub r#type: Option<String>,\n    \
+             /// the txids\n    \
+             pub tx: Vec<String>,\n}\n"
+        ));
+    }
+
+    #[test]
+    fn field_name_renames() {
+        assert_eq!(field_name("scriptPubKey"), "script_pub_key");
+        assert_eq!(field_name("in_active_chain"), "in_active_chain");
+        assert_eq!(field_name("type"), "r#type");
+        assert_eq!(field_name("1d"), "_1d");
+        assert_eq!(field_name("self"), "self_");
+        assert_eq!(field_name("Self"), "self_");
+        assert_eq!(field_name("crate"), "crate_");
+        assert_eq!(field_name("super"), "super_");
+    }
+}
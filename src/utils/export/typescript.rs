@@ -0,0 +1,334 @@
+use crate::utils::export::rust_types::{field_name, type_name};
+use crate::utils::model::{Argument, Command, Field, Shape};
+use std::collections::HashSet;
+
+// Words that can't label a tuple element as they are.
+const RESERVED: [&str; 12] = [
+    "break", "case", "class", "default", "delete", "function", "new", "null",
+    "return", "this", "type", "var",
+];
+
+/// TypeScript declarations for the params and response of every command in
+/// `commands`: `<Command>Params` labelled tuples, `<Command>Response`
+/// interfaces named like `rust_types` names its types, and for commands
+/// with several result variants a `<Command>Variant` union discriminated by
+/// what each variant is documented for.  `ZcashdMethods` maps each method
+/// name to both, for typing a JSON-RPC proxy.
+pub fn typescript_declarations(commands: &[Command], source: &str) -> String {
+    let mut declarations = Declarations::default();
+    for command in commands {
+        declarations.declare_command(command);
+    }
+    let mut methods = "\nexport interface ZcashdMethods {\n".to_string();
+    for command in commands {
+        let name = type_name(&command.name);
+        methods.push_str(&format!(
+            "  {}: {{ params: {}Params; result: {}Response }};\n",
+            command.name, name, name
+        ));
+    }
+    methods.push_str("}\n");
+    format!(
+        "// zcashd RPC types generated by quizface from `{}`.\n\
+         // Do not edit.\n{}{}",
+        source,
+        declarations.definitions.concat(),
+        methods
+    )
+}
+
+#[derive(Default)]
+struct Declarations {
+    definitions: Vec<String>,
+    declared: HashSet<String>,
+}
+
+impl Declarations {
+    fn declare_command(&mut self, command: &Command) {
+        let name = type_name(&command.name);
+        let mut doc = vec![format!("The response of `{}`.", command.name)];
+        if let Some(summary) = &command.summary {
+            doc.push(String::new());
+            doc.extend(summary.lines().map(String::from));
+        }
+        if command.deprecated {
+            doc.push("@deprecated".to_string());
+        }
+        // Without recorded arguments the params are unknown.
+        let params = if command.metadata.is_null() {
+            "unknown[]".to_string()
+        } else {
+            format!("[{}]", params(&command.arguments))
+        };
+        self.definitions.push(format!(
+            "\n{}export type {}Params = {};\n",
+            doc_comment(
+                &[format!("The positional params of `{}`.", command.name)],
+                ""
+            ),
+            name,
+            params
+        ));
+        let response = format!("{}Response", name);
+        match command.results.len() {
+            0 => self.definitions.push(format!(
+                "\n{}export type {} = unknown;\n",
+                doc_comment(&doc, ""),
+                response
+            )),
+            1 => self.declare_named(&response, &command.results[0], &doc),
+            _ => {
+                let mut variants = vec![];
+                let mut tagged = vec![];
+                for (index, shape) in command.results.iter().enumerate() {
+                    let variant = format!("{}{}", response, index);
+                    self.declare_named(&variant, shape, &[]);
+                    let tag = match command.result_conditions.get(index) {
+                        Some(Some(condition)) => format!("{:?}", condition),
+                        _ => index.to_string(),
+                    };
+                    tagged.push(format!(
+                        "  | {{ variant: {}; result: {} }}",
+                        tag, variant
+                    ));
+                    variants.push(variant);
+                }
+                self.definitions.push(format!(
+                    "\n{}export type {} = {};\n",
+                    doc_comment(&doc, ""),
+                    response,
+                    variants.join(" | ")
+                ));
+                self.definitions.push(format!(
+                    "\n/** `{}` responses, told apart by what they are \
+                     documented for. */\nexport type {}Variant =\n{};\n",
+                    command.name,
+                    name,
+                    tagged.join("\n")
+                ));
+            }
+        }
+    }
+
+    // Declares `name` as the type of `shape`: an interface if it is an
+    // object, an alias otherwise.
+    fn declare_named(&mut self, name: &str, shape: &Shape, doc: &[String]) {
+        self.declared.insert(name.to_string());
+        if let Shape::Object(fields) = shape {
+            self.declare_interface(name, fields, doc);
+        } else {
+            let ts_type = self.ts_type(shape, name);
+            self.definitions.push(format!(
+                "\n{}export type {} = {};\n",
+                doc_comment(doc, ""),
+                name,
+                ts_type
+            ));
+        }
+    }
+
+    fn declare_interface(
+        &mut self,
+        name: &str,
+        fields: &[Field],
+        doc: &[String],
+    ) {
+        self.declared.insert(name.to_string());
+        let mut members = String::new();
+        for field in fields {
+            let ts_type = self.ts_type(
+                &field.shape,
+                &format!("{}{}", name, type_name(&field.name)),
+            );
+            let mut field_doc = vec![];
+            if let Some(description) = &field.description {
+                field_doc.extend(description.lines().map(String::from));
+            }
+            if let Some(unit) = &field.unit {
+                field_doc.push(format!("Unit: {}", unit));
+            }
+            members.push_str(&doc_comment(&field_doc, "  "));
+            members.push_str(&format!(
+                "  {}{}: {};\n",
+                property_name(&field.name),
+                if field.optional { "?" } else { "" },
+                ts_type
+            ));
+        }
+        self.definitions.push(format!(
+            "\n{}export interface {} {{\n{}}}\n",
+            doc_comment(doc, ""),
+            name,
+            members
+        ));
+    }
+
+    // The TypeScript type of `shape`, declaring any interface it needs as
+    // `name`.
+    fn ts_type(&mut self, shape: &Shape, name: &str) -> String {
+        match shape {
+            Shape::Decimal => "number".to_string(),
+            Shape::String | Shape::Hexadecimal => "string".to_string(),
+            Shape::Bool => "boolean".to_string(),
+            Shape::Insufficient => "unknown".to_string(),
+            Shape::Enum(values) => values
+                .iter()
+                .map(|value| format!("{:?}", value))
+                .collect::<Vec<String>>()
+                .join(" | "),
+            Shape::Array(item) => {
+                let item_type = self.ts_type(item, &format!("{}Item", name));
+                if item_type.contains(' ') {
+                    format!("Array<{}>", item_type)
+                } else {
+                    format!("{}[]", item_type)
+                }
+            }
+            Shape::Map(key, value) => format!(
+                "{{ [{}: string]: {} }}",
+                key,
+                self.ts_type(value, &format!("{}Entry", name))
+            ),
+            Shape::Object(fields) => {
+                let name = self.unique(name);
+                self.declare_interface(&name, fields, &[]);
+                name
+            }
+        }
+    }
+
+    fn unique(&self, name: &str) -> String {
+        let mut unique = name.to_string();
+        let mut suffix = 1;
+        while self.declared.contains(&unique) {
+            suffix += 1;
+            unique = format!("{}{}", name, suffix);
+        }
+        unique
+    }
+}
+
+// Labelled tuple elements, e.g. `hash_height: string, verbosity?: number`.
+// An element can't be required after an optional one, so those after the
+// first optional one are all optional.
+fn params(arguments: &[Argument]) -> String {
+    let mut optional = false;
+    arguments
+        .iter()
+        .map(|argument| {
+            optional |= !argument.required;
+            let mut label = field_name(&argument.name)
+                .trim_start_matches("r#")
+                .to_string();
+            if RESERVED.contains(&label.as_str()) {
+                label.push('_');
+            }
+            format!(
+                "{}{}: {}",
+                label,
+                if optional { "?" } else { "" },
+                param_type(&argument.shape())
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn param_type(shape: &Shape) -> String {
+    match shape {
+        Shape::Decimal => "number".to_string(),
+        Shape::String | Shape::Hexadecimal => "string".to_string(),
+        Shape::Bool => "boolean".to_string(),
+        Shape::Array(item) => format!("{}[]", param_type(item)),
+        _ => "unknown".to_string(),
+    }
+}
+
+// Quoted unless it is an identifier.
+fn property_name(key: &str) -> String {
+    let identifier = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if identifier {
+        key.to_string()
+    } else {
+        format!("{:?}", key)
+    }
+}
+
+fn doc_comment(lines: &[String], indent: &str) -> String {
+    match lines.len() {
+        0 => String::new(),
+        1 => format!("{}/** {} */\n", indent, escape_doc(&lines[0])),
+        _ => {
+            let mut comment = format!("{}/**\n", indent);
+            for line in lines {
+                let line = escape_doc(line.trim());
+                if line.is_empty() {
+                    comment.push_str(&format!("{} *\n", indent));
+                } else {
+                    comment.push_str(&format!("{} * {}\n", indent, line));
+                }
+            }
+            comment.push_str(&format!("{} */\n", indent));
+            comment
+        }
+    }
+}
+
+fn escape_doc(line: &str) -> String {
+    line.replace("*/", "*\\/")
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn typescript_declarations_getblock_variants() {
+        let command = Command::from_json(
+            "getblock",
+            &json!([
+                "hexadecimal",
+                {"hash": "hexadecimal", "Option<tx>": ["String"]}
+            ]),
+            json!({
+                "arguments": [
+                    {"name": "hash|height", "type": "string",
+                     "required": true, "description": ""},
+                    {"name": "verbosity", "type": "numeric",
+                     "required": false, "description": ""}
+                ],
+                "fields": [{}, {"/hash": {"description": "the block hash"}}],
+                "result_conditions": ["verbosity = 0", "verbosity = 1"]
+            }),
+        );
+        let declarations = typescript_declarations(&[command], "test");
+        assert!(declarations.contains(
+            "export type GetblockParams = \
+             [hash_height: string, verbosity?: number];\n"
+        ));
+        assert!(declarations.contains(
+            "export interface GetblockResponse1 {\n  \
+             tx?: string[];\n  /** the block hash */\n  hash: string;\n}\n"
+        ));
+        assert!(declarations.contains(
+            "export type GetblockResponse = \
+             GetblockResponse0 | GetblockResponse1;\n"
+        ));
+        assert!(declarations.contains(
+            "export type GetblockVariant =\n  \
+             | { variant: \"verbosity = 0\"; result: GetblockResponse0 }\n  \
+             | { variant: \"verbosity = 1\"; result: GetblockResponse1 };\n"
+        ));
+        assert!(declarations.contains(
+            "  getblock: { params: GetblockParams; \
+             result: GetblockResponse };\n"
+        ));
+    }
+}
@@ -0,0 +1,316 @@
+use regex::Regex;
+use serde_json::{json, map::Map, Value};
+
+// Phrases in a field description, checked in order, and the unit a numeric
+// field carrying them is recorded with.
+const UNITS: [(&str, &str); 10] = [
+    (
+        r"(?i)seconds since .*(epoch|1970)",
+        "unix_timestamp_seconds",
+    ),
+    (r"(?i)\bZEC ?/ ?kB\b|\bZEC per kB\b", "ZEC/kB"),
+    (r"(?i)\b(zatoshis?|satoshis?)\b", "zatoshi"),
+    (r"\bZEC\b", "ZEC"),
+    (r"(?i)\bin kB\b|\bkilobytes\b", "kilobytes"),
+    (r"(?i)\bbytes?\b", "bytes"),
+    (r"(?i)\bmilliseconds\b|\bin ms\b", "milliseconds"),
+    (r"(?i)\bseconds\b", "seconds"),
+    (
+        r"(?i)\bsol(utions)?/s\b|\bsolutions per second\b",
+        "solutions_per_second",
+    ),
+    (r"(?i)\bhashes per second\b", "hashes_per_second"),
+];
+
+/// Documents every field of one scrubbed result variant, keyed by the JSON
+/// pointer of that field in the variant's interpretation.  Numeric fields
+/// additionally carry the `unit` and `sentinels` found in their description.
+///
+/// This walks the result the same way `annotate_result` does, but never
+/// panics: text it cannot make sense of is simply left undocumented.
+pub fn document_fields(result: &str) -> Map<String, Value> {
+    let mut fields = Map::new();
+    let mut result_chars = result.chars();
+    match result_chars.next() {
+        Some('{') => document_object(&mut result_chars, "", &mut fields),
+        Some('[') => document_array(&mut result_chars, "", &mut fields),
+        Some(_) => {
+            if let Some((raw_label, description)) = split_metadata(result) {
+                document_field("", &raw_label, &description, &mut fields);
+            }
+        }
+        None => (),
+    }
+    fields
+}
+
+fn document_object(
+    result_chars: &mut std::str::Chars,
+    path: &str,
+    fields: &mut Map<String, Value>,
+) {
+    let mut viewed = String::new();
+    let mut first_viewed = true;
+    while let Some(c) = result_chars.next() {
+        match c {
+            '}' => {
+                document_members(&viewed, path, first_viewed, fields);
+                return;
+            }
+            '[' | '{' => {
+                let (members, last_line) = split_last_line(&viewed);
+                document_members(members, path, first_viewed, fields);
+                first_viewed = false;
+                let ident = last_line
+                    .trim()
+                    .split(':')
+                    .next()
+                    .unwrap_or("")
+                    .trim()
+                    .trim_matches('"');
+                let inner_path = format!("{}/{}", path, escape(ident));
+                if c == '[' {
+                    document_array(result_chars, &inner_path, fields);
+                } else {
+                    document_object(result_chars, &inner_path, fields);
+                }
+                viewed.clear();
+            }
+            x => viewed.push(x),
+        }
+    }
+}
+
+fn document_array(
+    result_chars: &mut std::str::Chars,
+    path: &str,
+    fields: &mut Map<String, Value>,
+) {
+    let mut viewed = String::new();
+    let mut first_viewed = true;
+    let mut index = 0;
+    while let Some(c) = result_chars.next() {
+        match c {
+            ']' => {
+                let mut lines = viewed.lines();
+                if first_viewed {
+                    document_container(
+                        lines.next().unwrap_or(""),
+                        path,
+                        fields,
+                    );
+                }
+                if let Some((raw_label, description)) =
+                    lines.find_map(split_metadata)
+                {
+                    let item_path = format!("{}/{}", path, index);
+                    document_field(
+                        &item_path,
+                        &raw_label,
+                        &description,
+                        fields,
+                    );
+                }
+                return;
+            }
+            '[' | '{' => {
+                if first_viewed {
+                    document_container(
+                        viewed.lines().next().unwrap_or(""),
+                        path,
+                        fields,
+                    );
+                    first_viewed = false;
+                }
+                let item_path = format!("{}/{}", path, index);
+                if c == '[' {
+                    document_array(result_chars, &item_path, fields);
+                } else {
+                    document_object(result_chars, &item_path, fields);
+                }
+                index += 1;
+                viewed.clear();
+            }
+            x => viewed.push(x),
+        }
+    }
+}
+
+// Mirrors `bind_idents_labels`: the first line seen inside a container
+// describes the container itself unless it binds an identifier.
+fn document_members(
+    viewed: &str,
+    path: &str,
+    first_viewed: bool,
+    fields: &mut Map<String, Value>,
+) {
+    let mut lines = viewed.lines().peekable();
+    if first_viewed {
+        if let Some(first) = lines.peek() {
+            if !first.contains(':') {
+                document_container(first, path, fields);
+                lines.next();
+            }
+        }
+    }
+    for line in lines {
+        if let Some((ident, raw_label, description)) = split_member(line) {
+            let ident = if raw_label.contains(", optional") {
+                format!("Option<{}>", ident)
+            } else {
+                ident
+            };
+            let member_path = format!("{}/{}", path, escape(&ident));
+            document_field(&member_path, &raw_label, &description, fields);
+        }
+    }
+}
+
+fn document_container(line: &str, path: &str, fields: &mut Map<String, Value>) {
+    if let Some((raw_label, description)) = split_metadata(line) {
+        document_field(path, &raw_label, &description, fields);
+    }
+}
+
+fn document_field(
+    path: &str,
+    raw_label: &str,
+    description: &str,
+    fields: &mut Map<String, Value>,
+) {
+    let mut field = Map::new();
+    if !description.is_empty() {
+        field.insert("description".to_string(), json!(description));
+    }
+    if raw_label.starts_with("numeric") {
+        if let Some(unit) = find_unit(description) {
+            field.insert("unit".to_string(), json!(unit));
+        }
+        let sentinels = find_sentinels(description);
+        if !sentinels.is_empty() {
+            field.insert("sentinels".to_string(), Value::Array(sentinels));
+        }
+    }
+    if !field.is_empty() {
+        fields.insert(path.to_string(), Value::Object(field));
+    }
+}
+
+/// The unit `description` gives its numeric value in, e.g. `ZEC`.
+pub fn find_unit(description: &str) -> Option<&'static str> {
+    UNITS
+        .iter()
+        .find(|(pattern, _)| {
+            Regex::new(pattern)
+                .expect("Invalid regex!")
+                .is_match(description)
+        })
+        .map(|(_, unit)| *unit)
+}
+
+fn find_sentinels(description: &str) -> Vec<Value> {
+    Regex::new(r"(?:^|[\s,(;])(-?\d+(?:\.\d+)?) if ([^,;.)]+)")
+        .expect("Invalid regex!")
+        .captures_iter(description)
+        .filter_map(|caps| {
+            let value: Value = serde_json::from_str(&caps[1]).ok()?;
+            Some(json!({"value": value, "meaning": caps[2].trim()}))
+        })
+        .collect()
+}
+
+// `"ident": xx,   (numeric, optional) text` -> (ident, raw label, text)
+fn split_member(line: &str) -> Option<(String, String, String)> {
+    let trimmed = line.trim().trim_start_matches(',').trim();
+    let rest = trimmed.strip_prefix('"')?;
+    let (ident, after) = rest.split_at(rest.find('"')?);
+    if !after[1..].trim_start().starts_with(':') {
+        return None;
+    }
+    let (raw_label, description) = split_metadata(after)?;
+    Some((ident.to_string(), raw_label, description))
+}
+
+// `... (numeric) text` -> (raw label, text)
+fn split_metadata(line: &str) -> Option<(String, String)> {
+    let open = line.find('(')?;
+    let close = open + line[open..].find(')')?;
+    Some((
+        line[open + 1..close].trim().to_string(),
+        line[close + 1..].trim().to_string(),
+    ))
+}
+
+fn split_last_line(viewed: &str) -> (&str, &str) {
+    let trimmed = viewed.trim_end();
+    match trimmed.rfind('\n') {
+        Some(i) => (&trimmed[..i], &trimmed[i + 1..]),
+        None => ("", trimmed),
+    }
+}
+
+fn escape(ident: &str) -> String {
+    ident.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use crate::utils::test;
+
+    #[test]
+    fn document_fields_getinfo_units_and_sentinels() {
+        let result = test::HELP_GETINFO
+            .split("Result:")
+            .nth(1)
+            .unwrap()
+            .split("Examples:")
+            .next()
+            .unwrap()
+            .trim();
+        let fields = document_fields(result);
+        assert_eq!(fields["/paytxfee"]["unit"], "ZEC/kB");
+        assert_eq!(
+            fields["/Option<proxy>"]["description"],
+            "the proxy used by the server"
+        );
+        assert_eq!(fields["/unlocked_until"]["unit"], "unix_timestamp_seconds");
+        assert_eq!(
+            fields["/unlocked_until"]["sentinels"],
+            json!([{"value": 0, "meaning": "the wallet is locked"}])
+        );
+        assert!(fields["/errors"].get("unit").is_none());
+    }
+
+    #[test]
+    fn document_fields_nested_paths() {
+        let fields =
+            document_fields(test::HELP_GETBLOCKCHAININFO_RESULT_SCRUBBED);
+        assert_eq!(
+            fields["/softforks"]["description"],
+            "status of softforks in progress"
+        );
+        assert_eq!(
+            fields["/softforks/0/enforce/window"]["description"],
+            "maximum size of examined window of recent blocks"
+        );
+        assert_eq!(
+            fields["/upgrades/xxxx/activationheight"]["description"],
+            "block height of activation"
+        );
+        assert_eq!(
+            fields["/upgrades/xxxx"]["description"],
+            "branch ID of the upgrade"
+        );
+    }
+
+    #[test]
+    fn find_sentinels_block_confirmations() {
+        assert_eq!(
+            find_sentinels(
+                "The number of confirmations, or -1 if the block is not on the main chain"
+            ),
+            vec![json!({"value": -1, "meaning": "the block is not on the main chain"})]
+        );
+    }
+}
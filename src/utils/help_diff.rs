@@ -0,0 +1,496 @@
+//! Line by line differences between the raw help two zcashd versions give
+//! for each command, as logged under `logs/<version>/help_output/raw/`.
+//! Wording changes often document a change the interpretation misses, and
+//! are what stale prescrub and scrub rules trip over, so each command's
+//! differences come with the rules whose matching they changed.
+use crate::utils::prescrubbing::prescrub;
+use crate::utils::rules::{Change, RuleOutcome};
+use crate::utils::scrubbing::scrub;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+// Unchanged lines shown around each change.
+const CONTEXT: usize = 3;
+const MIN_COVERED: usize = 3;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Line {
+    Same(String),
+    Removed(String),
+    Added(String),
+}
+
+/// A run of changed lines and their context, starting at line `old_start`
+/// of the older help and `new_start` of the newer, counting from 1.
+#[derive(Debug, PartialEq)]
+pub struct Hunk {
+    pub old_start: usize,
+    pub new_start: usize,
+    /// With the ids of the rules whose correction covers the line.
+    pub lines: Vec<(Line, Vec<String>)>,
+}
+
+/// A rule that matches the help of one version but not of the other, or
+/// that matches different text in each.
+#[derive(Debug, PartialEq)]
+pub struct RuleEffect {
+    pub rule_id: String,
+    pub phase: &'static str,
+    pub effect: &'static str,
+}
+
+pub struct HelpDiff {
+    pub command: String,
+    /// `added`, `removed` or `changed`.
+    pub status: &'static str,
+    pub hunks: Vec<Hunk>,
+    pub rules: Vec<RuleEffect>,
+}
+
+/// The raw help logged in `log_dir`, e.g. `logs/v4.3.0_0.2.0`, by command.
+pub fn load_raw_help(log_dir: &Path) -> BTreeMap<String, String> {
+    let raw_dir = log_dir.join("help_output").join("raw");
+    std::fs::read_dir(&raw_dir)
+        .unwrap_or_else(|e| panic!("Couldn't read {:?}: {}", raw_dir, e))
+        .map(|entry| entry.expect("Couldn't read raw help entry!").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .map(|path| {
+            (
+                path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .expect("Invalid raw help file name!")
+                    .to_string(),
+                std::fs::read_to_string(&path)
+                    .expect("Couldn't read raw help!"),
+            )
+        })
+        .collect()
+}
+
+/// The differences between the help of every command in `old` or `new`,
+/// applying the rules of `old_version` to the older help and of
+/// `new_version` to the newer.  Commands with the same help are left out.
+pub fn diff_help(
+    old: &BTreeMap<String, String>,
+    new: &BTreeMap<String, String>,
+    old_version: &str,
+    new_version: &str,
+) -> Vec<HelpDiff> {
+    let mut commands = old.keys().chain(new.keys()).collect::<Vec<_>>();
+    commands.sort();
+    commands.dedup();
+    let mut diffs = vec![];
+    for command in commands {
+        let old_help = old.get(command).map(String::as_str).unwrap_or("");
+        let new_help = new.get(command).map(String::as_str).unwrap_or("");
+        if old_help == new_help {
+            continue;
+        }
+        let old_outcomes = rule_outcomes(command, old_help, old_version);
+        let new_outcomes = rule_outcomes(command, new_help, new_version);
+        let lines = diff_lines(old_help, new_help)
+            .into_iter()
+            .map(|line| {
+                let rules = match &line {
+                    Line::Same(_) => vec![],
+                    Line::Removed(text) => covering(&old_outcomes, text),
+                    Line::Added(text) => covering(&new_outcomes, text),
+                };
+                (line, rules)
+            })
+            .collect();
+        diffs.push(HelpDiff {
+            command: command.clone(),
+            status: if !old.contains_key(command) {
+                "added"
+            } else if !new.contains_key(command) {
+                "removed"
+            } else {
+                "changed"
+            },
+            hunks: hunks(lines),
+            rules: rule_effects(&old_outcomes, &new_outcomes),
+        });
+    }
+    diffs
+}
+
+// What the prescrub rules, and the scrub rules on the response section
+// they leave, make of `raw_command_help`.
+fn rule_outcomes(
+    command: &str,
+    raw_command_help: &str,
+    zcashd_version: &str,
+) -> Vec<RuleOutcome> {
+    if raw_command_help.is_empty() {
+        return vec![];
+    }
+    let (prescrubbed, mut outcomes) =
+        prescrub(command, raw_command_help, zcashd_version);
+    // Help without a response section is never scrubbed.
+    let response_delimiters =
+        regex::Regex::new(r"(?s)Result[:\s].*?Examples[:\s]")
+            .expect("Invalid regex");
+    if response_delimiters.is_match(&prescrubbed) {
+        let response = crate::partition_help_text(&prescrubbed)
+            .remove("response")
+            .unwrap_or_default();
+        outcomes.extend(scrub(command.to_string(), response, zcashd_version).1);
+    }
+    outcomes
+}
+
+// The rules whose correction starts on `line`, or spans it.  Lines are
+// matched only on text with at least `MIN_COVERED` letters or digits, so
+// that a bare `},` or `]` isn't put down to whichever rule corrected one.
+fn covering(outcomes: &[RuleOutcome], line: &str) -> Vec<String> {
+    let line = line.trim();
+    outcomes
+        .iter()
+        .filter(|outcome| {
+            outcome
+                .change
+                .as_ref()
+                .is_some_and(|Change { before, .. }| {
+                    before.trim_end_matches("...").lines().map(str::trim).any(
+                        |covered| {
+                            (significant(covered) && line.contains(covered))
+                                || (significant(line) && covered.contains(line))
+                        },
+                    )
+                })
+        })
+        .map(|outcome| outcome.rule_id.clone())
+        .collect()
+}
+
+fn significant(text: &str) -> bool {
+    text.chars().filter(|c| c.is_alphanumeric()).count() >= MIN_COVERED
+}
+
+fn rule_effects(old: &[RuleOutcome], new: &[RuleOutcome]) -> Vec<RuleEffect> {
+    let mut effects = vec![];
+    for outcome in old {
+        let newer = new
+            .iter()
+            .find(|o| o.rule_id == outcome.rule_id && o.phase == outcome.phase);
+        let effect = match newer {
+            Some(newer) if outcome.matched() && !newer.matched() => {
+                "stopped matching"
+            }
+            Some(newer) if !outcome.matched() && newer.matched() => {
+                "started matching"
+            }
+            Some(newer) if outcome.change != newer.change => {
+                "matches different text"
+            }
+            Some(_) => continue,
+            None if outcome.matched() => "no longer applies",
+            None => continue,
+        };
+        effects.push(RuleEffect {
+            rule_id: outcome.rule_id.clone(),
+            phase: outcome.phase.name(),
+            effect,
+        });
+    }
+    for outcome in new {
+        if outcome.matched()
+            && !old.iter().any(|o| {
+                o.rule_id == outcome.rule_id && o.phase == outcome.phase
+            })
+        {
+            effects.push(RuleEffect {
+                rule_id: outcome.rule_id.clone(),
+                phase: outcome.phase.name(),
+                effect: "newly applies",
+            });
+        }
+    }
+    effects
+}
+
+/// The lines of `old` and `new` in order, by a longest common subsequence.
+pub fn diff_lines(old: &str, new: &str) -> Vec<Line> {
+    let old = old.lines().collect::<Vec<&str>>();
+    let new = new.lines().collect::<Vec<&str>>();
+    // `common[i][j]`: the longest common subsequence of `old[i..]` and
+    // `new[j..]`.
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut lines = vec![];
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(Line::Same(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if j == new.len()
+            || (i < old.len() && common[i + 1][j] >= common[i][j + 1])
+        {
+            lines.push(Line::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(Line::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+    lines
+}
+
+// The changed lines of `lines` with `CONTEXT` lines around them, merging
+// changes that close.
+fn hunks(lines: Vec<(Line, Vec<String>)>) -> Vec<Hunk> {
+    let changed = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (line, _))| !matches!(line, Line::Same(_)))
+        .map(|(index, _)| index)
+        .collect::<Vec<usize>>();
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    for index in changed {
+        let start = index.saturating_sub(CONTEXT);
+        let end = (index + CONTEXT + 1).min(lines.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+    let mut hunks = vec![];
+    let (mut old_line, mut new_line, mut index) = (1, 1, 0);
+    let mut lines = lines.into_iter();
+    for (start, end) in ranges {
+        for (line, _) in lines.by_ref().take(start - index) {
+            match line {
+                Line::Same(_) => {
+                    old_line += 1;
+                    new_line += 1;
+                }
+                Line::Removed(_) => old_line += 1,
+                Line::Added(_) => new_line += 1,
+            }
+        }
+        let hunk_lines = lines.by_ref().take(end - start).collect::<Vec<_>>();
+        let hunk = Hunk {
+            old_start: old_line,
+            new_start: new_line,
+            lines: hunk_lines,
+        };
+        for (line, _) in &hunk.lines {
+            match line {
+                Line::Same(_) => {
+                    old_line += 1;
+                    new_line += 1;
+                }
+                Line::Removed(_) => old_line += 1,
+                Line::Added(_) => new_line += 1,
+            }
+        }
+        hunks.push(hunk);
+        index = end;
+    }
+    hunks
+}
+
+pub fn help_diff_to_json(diffs: &[HelpDiff]) -> Value {
+    diffs
+        .iter()
+        .map(|diff| {
+            (
+                diff.command.clone(),
+                json!({
+                    "status": diff.status,
+                    "hunks": diff.hunks.iter().map(|hunk| json!({
+                        "old_start": hunk.old_start,
+                        "new_start": hunk.new_start,
+                        "lines": hunk.lines.iter().map(|(line, rules)| {
+                            let (kind, text) = match line {
+                                Line::Same(text) => ("same", text),
+                                Line::Removed(text) => ("removed", text),
+                                Line::Added(text) => ("added", text),
+                            };
+                            json!({"kind": kind, "text": text, "rules": rules})
+                        }).collect::<Vec<Value>>(),
+                    })).collect::<Vec<Value>>(),
+                    "rules": diff.rules.iter().map(|effect| json!({
+                        "rule_id": effect.rule_id,
+                        "phase": effect.phase,
+                        "effect": effect.effect,
+                    })).collect::<Vec<Value>>(),
+                }),
+            )
+        })
+        .collect::<serde_json::Map<String, Value>>()
+        .into()
+}
+
+/// A Markdown report with a unified diff per command, followed by the
+/// changed lines a rule corrects and the rules whose matching changed.
+pub fn help_diff_markdown(diffs: &[HelpDiff], old: &str, new: &str) -> String {
+    let mut markdown = format!("# Help changes from {} to {}\n", old, new);
+    if diffs.is_empty() {
+        markdown.push_str("\nNo changes.\n");
+    }
+    for diff in diffs {
+        markdown.push_str(&format!(
+            "\n## `{}` ({})\n\n```diff\n",
+            diff.command, diff.status
+        ));
+        let mut covered = vec![];
+        for hunk in &diff.hunks {
+            let old_count = hunk
+                .lines
+                .iter()
+                .filter(|(line, _)| !matches!(line, Line::Added(_)))
+                .count();
+            let new_count = hunk
+                .lines
+                .iter()
+                .filter(|(line, _)| !matches!(line, Line::Removed(_)))
+                .count();
+            markdown.push_str(&format!(
+                "@@ -{},{} +{},{} @@\n",
+                // Like `diff -u`, an empty side starts before its first line.
+                hunk.old_start - usize::from(old_count == 0),
+                old_count,
+                hunk.new_start - usize::from(new_count == 0),
+                new_count
+            ));
+            let (mut old_line, mut new_line) = (hunk.old_start, hunk.new_start);
+            for (line, rules) in &hunk.lines {
+                let (marker, text, number) = match line {
+                    Line::Same(text) => {
+                        old_line += 1;
+                        new_line += 1;
+                        (' ', text, 0)
+                    }
+                    Line::Removed(text) => {
+                        old_line += 1;
+                        ('-', text, old_line - 1)
+                    }
+                    Line::Added(text) => {
+                        new_line += 1;
+                        ('+', text, new_line - 1)
+                    }
+                };
+                markdown.push_str(&format!("{}{}\n", marker, text));
+                if !rules.is_empty() {
+                    covered.push(format!(
+                        "- {}{}: {}\n",
+                        marker,
+                        number,
+                        rules
+                            .iter()
+                            .map(|rule| format!("`{}`", rule))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ));
+                }
+            }
+        }
+        markdown.push_str("```\n");
+        if !covered.is_empty() {
+            markdown.push_str("\nChanged lines corrected by rules:\n\n");
+            markdown.push_str(&covered.concat());
+        }
+        if !diff.rules.is_empty() {
+            markdown.push_str("\nRules affected:\n\n");
+            for effect in &diff.rules {
+                markdown.push_str(&format!(
+                    "- {} rule `{}` {}\n",
+                    effect.phase, effect.rule_id, effect.effect
+                ));
+            }
+        }
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use crate::utils::rules::Phase;
+
+    #[test]
+    fn diff_lines_and_hunks() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk";
+        let lines = diff_lines(old, new);
+        assert_eq!(
+            lines[..3],
+            [
+                Line::Same("a".to_string()),
+                Line::Removed("b".to_string()),
+                Line::Added("B".to_string())
+            ]
+        );
+        let hunks =
+            hunks(lines.into_iter().map(|line| (line, vec![])).collect());
+        assert_eq!(hunks.len(), 2);
+        assert_eq!((hunks[0].old_start, hunks[0].new_start), (1, 1));
+        assert_eq!(hunks[0].lines.len(), 6);
+        assert_eq!((hunks[1].old_start, hunks[1].new_start), (8, 8));
+        assert_eq!(hunks[1].lines.last().unwrap().0, Line::Added("k".into()));
+    }
+
+    #[test]
+    fn diff_help_marks_rules() {
+        let old_help = "getblock \"hash\"\n\nResult (for verbosity = 0):\n\
+                        \"data\"  (string)\n\nExamples:\n> zcash-cli getblock";
+        let new_help = "getblock \"hash\"\n\nResult (verbosity 0):\n\
+                        \"data\"  (string)\n\nExamples:\n> zcash-cli getblock";
+        let mut old = BTreeMap::new();
+        old.insert("getblock".to_string(), old_help.to_string());
+        let mut new = BTreeMap::new();
+        new.insert("getblock".to_string(), new_help.to_string());
+        let diffs = diff_help(&old, &new, "v4.3.0", "v4.3.1");
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].status, "changed");
+        assert_eq!(
+            diffs[0].hunks[0].lines[2],
+            (
+                Line::Removed("Result (for verbosity = 0):".to_string()),
+                vec!["getblock/result-header".to_string()]
+            )
+        );
+        assert!(diffs[0].rules.contains(&RuleEffect {
+            rule_id: "getblock/result-header".to_string(),
+            phase: "prescrub",
+            effect: "stopped matching",
+        }));
+        assert!(help_diff_markdown(&diffs, "v4.3.0", "v4.3.1").contains(
+            "- prescrub rule `getblock/result-header` stopped matching\n"
+        ));
+    }
+
+    #[test]
+    fn covering_ignores_short_lines() {
+        let outcome = RuleOutcome {
+            rule_id: "getrawmempool/drop-ellipsis".to_string(),
+            command: "getrawmempool".to_string(),
+            phase: Phase::Scrub,
+            default: false,
+            change: Some(Change::new(
+                "        \"transactionid\",    (string) parent id\n       ... ]\n  }, ...",
+                "",
+            )),
+        };
+        let outcomes = [outcome];
+        assert_eq!(
+            covering(&outcomes, "  \"transactionid\",    (string) parent id"),
+            vec!["getrawmempool/drop-ellipsis".to_string()]
+        );
+        assert!(covering(&outcomes, "  },").is_empty());
+        assert!(covering(&outcomes, "]").is_empty());
+        assert!(covering(&outcomes, "").is_empty());
+    }
+}
@@ -0,0 +1,234 @@
+//! When each command and each field of its results appeared, changed type
+//! or disappeared across several zcashd versions, so that clients
+//! supporting a range of them know what to gate on which version.
+use crate::utils::diff::flatten;
+use crate::utils::model::Command;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// The node of one version, its type and whether it is optional.
+type Cell = Option<(String, bool)>;
+
+pub struct CommandHistory {
+    /// Whether each version has the command.
+    pub present: Vec<bool>,
+    /// By result variant and path, e.g. `(0, "tx[].txid")`.
+    pub fields: BTreeMap<(usize, String), Vec<Cell>>,
+}
+
+pub struct History {
+    pub versions: Vec<String>,
+    pub commands: BTreeMap<String, CommandHistory>,
+}
+
+impl History {
+    /// `runs` are the commands each version interpreted, oldest first.
+    pub fn new(runs: &[(String, Vec<Command>)]) -> History {
+        let mut commands: BTreeMap<String, CommandHistory> = BTreeMap::new();
+        for (index, (_, run)) in runs.iter().enumerate() {
+            for command in run {
+                let history = commands
+                    .entry(command.name.clone())
+                    .or_insert_with(|| CommandHistory {
+                        present: vec![false; runs.len()],
+                        fields: BTreeMap::new(),
+                    });
+                history.present[index] = true;
+                for (variant, shape) in command.results.iter().enumerate() {
+                    for (path, node) in flatten(shape) {
+                        history
+                            .fields
+                            .entry((variant, path))
+                            .or_insert_with(|| vec![None; runs.len()])[index] =
+                            Some(node);
+                    }
+                }
+            }
+        }
+        History {
+            versions: runs.iter().map(|(version, _)| version.clone()).collect(),
+            commands,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let commands = self
+            .commands
+            .iter()
+            .map(|(name, history)| {
+                let fields = history
+                    .fields
+                    .iter()
+                    .map(|((variant, path), cells)| {
+                        json!({
+                            "variant": variant,
+                            "path": path,
+                            "types": cells
+                                .iter()
+                                .map(|cell| match cell {
+                                    Some((label, optional)) => json!({
+                                        "type": label,
+                                        "optional": optional
+                                    }),
+                                    None => Value::Null,
+                                })
+                                .collect::<Vec<Value>>(),
+                            "events": self.events(cells, |cell| {
+                                cell.as_ref().map(|(label, _)| label.clone())
+                            }),
+                        })
+                    })
+                    .collect::<Vec<Value>>();
+                (
+                    name.clone(),
+                    json!({
+                        "present": history.present,
+                        "events": self.events(&history.present, |present| {
+                            present.then(String::new)
+                        }),
+                        "fields": fields,
+                    }),
+                )
+            })
+            .collect::<serde_json::Map<String, Value>>();
+        json!({ "versions": self.versions, "commands": commands })
+    }
+
+    // The versions in which `cells` appeared, changed type or disappeared,
+    // `label` being the type of a cell if it is there.
+    fn events<T>(
+        &self,
+        cells: &[T],
+        label: impl Fn(&T) -> Option<String>,
+    ) -> Vec<Value> {
+        let mut events = vec![];
+        let mut previous: Option<String> = None;
+        for (version, cell) in self.versions.iter().zip(cells) {
+            let current = label(cell);
+            match (&previous, &current) {
+                (None, Some(_)) => {
+                    events.push(json!({"version": version, "event": "added"}))
+                }
+                (Some(_), None) => {
+                    events.push(json!({"version": version, "event": "removed"}))
+                }
+                (Some(old), Some(new)) if old != new => events.push(json!({
+                    "version": version,
+                    "event": "type_changed",
+                    "old": old,
+                    "new": new
+                })),
+                _ => (),
+            }
+            previous = current;
+        }
+        events
+    }
+
+    /// A Markdown table per command of the type of each field in each
+    /// version, `?` marking optional ones and `-` absent ones.
+    pub fn to_markdown(&self) -> String {
+        let header = format!(
+            "| Field | {} |\n|---|{}\n",
+            self.versions.join(" | "),
+            "---|".repeat(self.versions.len())
+        );
+        let mut markdown = format!(
+            "# Field history from {} to {}\n",
+            self.versions.first().map(String::as_str).unwrap_or("-"),
+            self.versions.last().map(String::as_str).unwrap_or("-")
+        );
+        for (name, history) in &self.commands {
+            markdown.push_str(&format!("\n## `{}`\n\n{}", name, header));
+            markdown.push_str(&format!(
+                "| (command) | {} |\n",
+                history
+                    .present
+                    .iter()
+                    .map(|present| if *present { "yes" } else { "-" })
+                    .collect::<Vec<&str>>()
+                    .join(" | ")
+            ));
+            let variants = history
+                .fields
+                .keys()
+                .map(|(variant, _)| variant)
+                .max()
+                .map_or(0, |last| last + 1);
+            for ((variant, path), cells) in &history.fields {
+                let path = if path.is_empty() { "(result)" } else { path };
+                // Told apart by variant only where there are several.
+                let field = if variants > 1 {
+                    format!("{}: `{}`", variant, path)
+                } else {
+                    format!("`{}`", path)
+                };
+                markdown.push_str(&format!(
+                    "| {} | {} |\n",
+                    field.replace('|', "\\|"),
+                    cells
+                        .iter()
+                        .map(|cell| match cell {
+                            Some((label, true)) => format!("{}?", label),
+                            Some((label, false)) => label.clone(),
+                            None => "-".to_string(),
+                        })
+                        .collect::<Vec<String>>()
+                        .join(" | ")
+                ));
+            }
+        }
+        markdown
+    }
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+
+    #[test]
+    fn history_field_events_across_versions() {
+        let getinfo = |interpretation: Value| {
+            Command::from_json("getinfo", &interpretation, Value::Null)
+        };
+        let runs = vec![
+            ("v4.2.0".to_string(), vec![]),
+            (
+                "v4.3.0".to_string(),
+                vec![getinfo(json!([{"balance": "Decimal"}]))],
+            ),
+            (
+                "v4.4.0".to_string(),
+                vec![getinfo(json!([{"balance": "String",
+                                     "Option<proxy>": "String"}]))],
+            ),
+            (
+                "v4.5.0".to_string(),
+                vec![getinfo(json!([{"Option<proxy>": "String"}]))],
+            ),
+        ];
+        let history = History::new(&runs);
+        let json = history.to_json();
+        assert_eq!(
+            json["commands"]["getinfo"]["present"],
+            json!([false, true, true, true])
+        );
+        assert_eq!(
+            json["commands"]["getinfo"]["fields"][1]["events"],
+            json!([
+                {"version": "v4.3.0", "event": "added"},
+                {"version": "v4.4.0", "event": "type_changed",
+                 "old": "numeric", "new": "string"},
+                {"version": "v4.5.0", "event": "removed"}
+            ])
+        );
+        assert!(history.to_markdown().contains(
+            "| Field | v4.2.0 | v4.3.0 | v4.4.0 | v4.5.0 |\n\
+             |---|---|---|---|---|\n\
+             | (command) | - | yes | yes | yes |\n\
+             | `(result)` | - | object | object | object |\n\
+             | `balance` | - | numeric | string | - |\n\
+             | `proxy` | - | - | string? | string? |\n"
+        ));
+    }
+}
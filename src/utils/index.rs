@@ -0,0 +1,143 @@
+//! The `index.json` quizface writes next to the interpretations of a run,
+//! so that tooling can tell a complete run from a partial one and which
+//! interpretations changed without reading them all.
+use crate::utils::rules::RuleOutcome;
+use serde_json::{json, map::Map, Value};
+use sha2::{Digest, Sha256};
+
+pub struct RunIndex {
+    zcashd_version: String,
+    quizface_version: String,
+    commands: Map<String, Value>,
+}
+
+impl RunIndex {
+    pub fn new(zcashd_version: &str, quizface_version: &str) -> RunIndex {
+        RunIndex {
+            zcashd_version: zcashd_version.to_string(),
+            quizface_version: quizface_version.to_string(),
+            commands: Map::new(),
+        }
+    }
+
+    /// Records how interpreting `command` went.  `interpretation` is the
+    /// one recorded, or why there is none.
+    pub fn record(
+        &mut self,
+        command: &str,
+        category: Option<&str>,
+        raw_command_help: &str,
+        interpretation: Result<&str, &str>,
+        outcomes: &[RuleOutcome],
+    ) {
+        let mut warnings = outcomes
+            .iter()
+            .filter(|o| o.is_stale())
+            .map(|o| {
+                format!("{} rule '{}' did not match", o.phase.name(), o.rule_id)
+            })
+            .collect::<Vec<String>>();
+        let entry = match interpretation {
+            Ok(interpretation) => {
+                let insufficient =
+                    interpretation.matches("INSUFFICIENT").count();
+                if insufficient > 0 {
+                    warnings.push(format!(
+                        "{} documented too poorly to interpret",
+                        insufficient
+                    ));
+                }
+                json!({
+                    "category": category,
+                    "variants": serde_json::from_str::<Value>(interpretation)
+                        .ok()
+                        .and_then(|variants| variants.as_array().map(Vec::len))
+                        .unwrap_or(0),
+                    "status": "ok",
+                    "warnings": warnings,
+                    "raw_help_sha256": hex(&Sha256::digest(raw_command_help)),
+                    "interpretation_sha256": hex(&Sha256::digest(interpretation)),
+                })
+            }
+            Err(failure) => {
+                warnings.push(failure.to_string());
+                json!({
+                    "category": category,
+                    "variants": 0,
+                    "status": "failed",
+                    "warnings": warnings,
+                    "raw_help_sha256": hex(&Sha256::digest(raw_command_help)),
+                    "interpretation_sha256": null,
+                })
+            }
+        };
+        self.commands.insert(command.to_string(), entry);
+    }
+
+    /// `complete` is whether the run got through every command it was
+    /// asked to interpret.
+    pub fn to_json(&self, complete: bool) -> Value {
+        json!({
+            "zcashd_version": self.zcashd_version,
+            "quizface_version": self.quizface_version,
+            "complete": complete,
+            "commands": self.commands,
+        })
+    }
+}
+
+// The lowercase hex of `digest`, as `sha256sum` prints it.
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use crate::utils::rules::Phase;
+
+    #[test]
+    fn run_index_records_status_and_warnings() {
+        let mut index = RunIndex::new("v4.3.0", "0.2.0");
+        let stale = RuleOutcome {
+            rule_id: "getblock/result-header".to_string(),
+            command: "getblock".to_string(),
+            phase: Phase::Prescrub,
+            default: false,
+            change: None,
+        };
+        index.record(
+            "getblock",
+            Some("Blockchain"),
+            "getblock \"hash\"",
+            Ok(r#"["hexadecimal", {"tx": "INSUFFICIENT"}]"#),
+            &[stale],
+        );
+        index.record(
+            "getinfo",
+            None,
+            "getinfo",
+            Err("Label 'n' is invalid"),
+            &[],
+        );
+        let json = index.to_json(false);
+        assert_eq!(json["complete"], false);
+        assert_eq!(json["commands"]["getblock"]["variants"], 2);
+        assert_eq!(
+            json["commands"]["getblock"]["warnings"],
+            json!([
+                "prescrub rule 'getblock/result-header' did not match",
+                "1 documented too poorly to interpret"
+            ])
+        );
+        assert_eq!(json["commands"]["getinfo"]["status"], "failed");
+        assert_eq!(
+            json["commands"]["getinfo"]["interpretation_sha256"],
+            Value::Null
+        );
+        assert_eq!(
+            json["commands"]["getinfo"]["raw_help_sha256"],
+            "e2148bde9642face6de8ce2f94dda5de7d6e19b60c61380f7c532cbe3b68e8c8"
+        );
+    }
+}
@@ -0,0 +1,285 @@
+//! Checks raw help text against the conventions `partition_help_text` and
+//! `annotate_result` rely on, so new RPCs can be checked before release
+//! rather than corrected with rules afterwards.
+use crate::partition_help_text;
+use regex::Regex;
+
+// The first word of a field's parenthesised type.
+const TYPE_WORDS: [&str; 8] = [
+    "numeric",
+    "string",
+    "boolean",
+    "hexadecimal",
+    "object",
+    "array",
+    "json",
+    "enum",
+];
+
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    /// 1-based line of the raw help.
+    pub line: usize,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn new(line: usize, message: &str) -> Diagnostic {
+        Diagnostic {
+            line,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Whether `raw_command_help` has exactly one `Result:` line followed by a
+/// non-empty result and an `Examples:` line, and starts with `command`.
+pub fn blessed_check(raw_command_help: &str, command: &str) -> bool {
+    let delimiter_test_1: Vec<&str> =
+        raw_command_help.split("Result:\n").collect();
+    let delimiter_test_2: Vec<&str> =
+        raw_command_help.split("Examples:\n").collect();
+    if delimiter_test_1.len() == 2 && delimiter_test_2.len() != 1 {
+        let split_command_help =
+            raw_command_help.split("Result:\n").collect::<Vec<&str>>()[1]
+                .split("Examples:\n")
+                .collect::<Vec<&str>>()[0]
+                .trim();
+        !split_command_help.is_empty()
+            && raw_command_help
+                .lines()
+                .next()
+                .unwrap()
+                .starts_with(command)
+    } else {
+        false
+    }
+}
+
+/// Lints the raw help of `command`, returning diagnostics in line order.
+pub fn lint_help(command: &str, raw_command_help: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    for (number, line) in raw_command_help.lines().enumerate() {
+        if let Some(c) = line.chars().find(|c| !c.is_ascii()) {
+            diagnostics.push(Diagnostic::new(
+                number + 1,
+                &format!("non-ASCII character '{}'", c),
+            ));
+        }
+    }
+    if !raw_command_help
+        .lines()
+        .next()
+        .is_some_and(|line| line.starts_with(command))
+    {
+        diagnostics.push(Diagnostic::new(
+            1,
+            &format!("the first line does not start with `{}`", command),
+        ));
+    }
+    let headers = lint_headers(raw_command_help, &mut diagnostics);
+    if headers && blessed_check(raw_command_help, command) {
+        let response = &partition_help_text(raw_command_help)["response"];
+        let offset = raw_command_help[..raw_command_help
+            .find(response.as_str())
+            .expect("Response is not part of the help!")]
+            .matches('\n')
+            .count();
+        lint_response(response, offset, &mut diagnostics);
+    }
+    diagnostics.sort_by_key(|d| d.line);
+    diagnostics
+}
+
+// Returns whether there is exactly one `Result:` and `Examples:` header,
+// in that order.
+fn lint_headers(
+    raw_command_help: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> bool {
+    let header_lines = |pattern| {
+        let header = Regex::new(pattern).expect("Invalid regex!");
+        raw_command_help
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| header.is_match(line))
+            .map(|(number, _)| number + 1)
+            .collect::<Vec<usize>>()
+    };
+    let results = header_lines(r"^Result\b.*:?\s*$");
+    let examples = header_lines(r"^Examples\b.*:?\s*$");
+    for (name, lines) in [("Result:", &results), ("Examples:", &examples)] {
+        match lines.len() {
+            0 => diagnostics
+                .push(Diagnostic::new(1, &format!("no `{}` header", name))),
+            1 => (),
+            _ => {
+                for line in &lines[1..] {
+                    diagnostics.push(Diagnostic::new(
+                        *line,
+                        &format!("more than one `{}` header", name),
+                    ))
+                }
+            }
+        }
+    }
+    for line in results.iter().chain(examples.iter()) {
+        let text = raw_command_help.lines().nth(line - 1).unwrap().trim_end();
+        if !(text == "Result:" || text == "Examples:") {
+            diagnostics.push(Diagnostic::new(
+                *line,
+                &format!("`{}` is not a bare `Result:` or `Examples:`", text),
+            ));
+        }
+    }
+    match (results.first(), examples.first()) {
+        (Some(result), Some(example)) if result > example => {
+            diagnostics.push(Diagnostic::new(
+                *example,
+                "`Examples:` comes before `Result:`",
+            ));
+            false
+        }
+        _ => results.len() == 1 && examples.len() == 1,
+    }
+}
+
+// `offset` is the number of lines of raw help before `response`.
+fn lint_response(
+    response: &str,
+    offset: usize,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let member = Regex::new(r#"^"[^"]+"\s*:"#).expect("Invalid regex!");
+    let quoted_key = Regex::new(r#"^"[^"]+""#).expect("Invalid regex!");
+    let mut open: Vec<(char, usize)> = vec![];
+    for (number, line) in response.lines().enumerate().skip(1) {
+        let line_number = offset + number + 1;
+        let trimmed = line.trim();
+        let container = open.last().map(|(c, _)| *c);
+        let is_field = !trimmed.is_empty()
+            && !trimmed.starts_with(['}', ']', ',', '.'])
+            && trimmed != "{"
+            && trimmed != "[";
+        if is_field {
+            let opens_container = trimmed.starts_with(['{', '[']);
+            if container == Some('{') {
+                if !quoted_key.is_match(trimmed) {
+                    diagnostics.push(Diagnostic::new(
+                        line_number,
+                        "field without a quoted key",
+                    ));
+                } else if !member.is_match(trimmed) {
+                    diagnostics.push(Diagnostic::new(
+                        line_number,
+                        "no colon after the key",
+                    ));
+                }
+            }
+            if !opens_container || trimmed.contains('(') {
+                lint_type(trimmed, line_number, diagnostics);
+            }
+        }
+        for c in line.chars() {
+            match c {
+                '{' | '[' => open.push((c, line_number)),
+                '}' | ']' => {
+                    let opener = if c == '}' { '{' } else { '[' };
+                    match open.iter().rposition(|(o, _)| *o == opener) {
+                        // Whatever was opened since was never closed.
+                        Some(position) => {
+                            for (o, l) in open.drain(position..).skip(1) {
+                                diagnostics.push(Diagnostic::new(
+                                    l,
+                                    &format!("'{}' is never closed", o),
+                                ));
+                            }
+                        }
+                        None => diagnostics.push(Diagnostic::new(
+                            line_number,
+                            &format!("unmatched '{}'", c),
+                        )),
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+    for (c, line_number) in open {
+        diagnostics.push(Diagnostic::new(
+            line_number,
+            &format!("'{}' is never closed", c),
+        ));
+    }
+}
+
+fn lint_type(
+    line: &str,
+    line_number: usize,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let type_start = match line.find('(') {
+        Some(start) => start + 1,
+        None => {
+            diagnostics.push(Diagnostic::new(
+                line_number,
+                "field without a parenthesised type",
+            ));
+            return;
+        }
+    };
+    let type_word = line[type_start..]
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or("");
+    if !TYPE_WORDS.contains(&type_word) {
+        diagnostics.push(Diagnostic::new(
+            line_number,
+            &format!("unknown type word '{}'", type_word),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use crate::utils::test;
+
+    #[test]
+    fn lint_help_getinfo_is_clean() {
+        assert_eq!(
+            lint_help("getinfo", test::HELP_GETINFO.trim_start()),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn lint_help_reports_line_numbers() {
+        let diagnostics = lint_help("badhelp", test::LINT_BADHELP);
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::new(6, "unknown type word 'number'"),
+                Diagnostic::new(7, "no colon after the key"),
+                Diagnostic::new(8, "non-ASCII character '\u{2019}'"),
+                Diagnostic::new(8, "field without a parenthesised type"),
+                Diagnostic::new(9, "field without a quoted key"),
+                Diagnostic::new(10, "'[' is never closed"),
+                Diagnostic::new(12, "unmatched ']'"),
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_help_two_results() {
+        let diagnostics = lint_help("getblock", test::LINT_TWO_RESULTS);
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::new(3, "`Result (for verbosity = 0):` is not a bare `Result:` or `Examples:`"),
+                Diagnostic::new(6, "more than one `Result:` header"),
+                Diagnostic::new(6, "`Result (for verbosity = 1):` is not a bare `Result:` or `Examples:`"),
+            ]
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use std::fs;
 use std::path::Path;
-const QUIZFACE_VERSION: &'static str = env!("CARGO_PKG_VERSION");
+pub const QUIZFACE_VERSION: &'static str = env!("CARGO_PKG_VERSION");
 
-fn name_logdirs() -> (String, String, String) {
+fn name_logdirs() -> (String, String, String, String, String) {
     let log_parent_template: String =
         format!("./logs/{}/", create_version_name());
     let master_name: String =
@@ -10,7 +10,17 @@ fn name_logdirs() -> (String, String, String) {
     let base_name: String = format!("{}help_output/raw/", log_parent_template);
     let blessed_name: String =
         format!("{}blessed_commands/", log_parent_template);
-    (master_name, base_name, blessed_name)
+    let prescrubbed_name: String =
+        format!("{}help_output/prescrubbed/", log_parent_template);
+    let scrubbed_name: String =
+        format!("{}help_output/scrubbed/", log_parent_template);
+    (
+        master_name,
+        base_name,
+        blessed_name,
+        prescrubbed_name,
+        scrubbed_name,
+    )
 }
 
 pub fn get_zcashd_version() -> String {
@@ -34,6 +44,17 @@ pub fn create_version_name() -> String {
     format!("{}_{}", get_zcashd_version(), QUIZFACE_VERSION)
 }
 
+/// The zcashd version of the output or log dir of a run, named by
+/// `create_version_name`, e.g. `v4.3.0` for `output/v4.3.0_0.2.0`.
+pub fn zcashd_version_of(run_dir: &str) -> String {
+    Path::new(run_dir)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.split('_').next())
+        .expect("Invalid run dir!")
+        .to_string()
+}
+
 pub fn create_log_dirs() {
     fs::create_dir_all(Path::new(&name_logdirs().0))
         .expect("error creating master dir!");
@@ -41,6 +62,10 @@ pub fn create_log_dirs() {
         .expect("error creating commands dir!");
     fs::create_dir_all(Path::new(&name_logdirs().2))
         .expect("error creating blessed dir!");
+    fs::create_dir_all(Path::new(&name_logdirs().3))
+        .expect("error creating prescrubbed dir!");
+    fs::create_dir_all(Path::new(&name_logdirs().4))
+        .expect("error creating scrubbed dir!");
 }
 
 pub fn log_masterhelp_output(raw_help: &str) {
@@ -56,6 +81,22 @@ pub fn log_raw_output(command: &str, raw_command_help: String) {
     .expect("panic during fs::write command help!");
 }
 
+pub fn log_prescrubbed_output(command: &str, prescrubbed_command_help: &str) {
+    fs::write(
+        format!("{}{}.txt", name_logdirs().3, &command),
+        prescrubbed_command_help,
+    )
+    .expect("panic during fs::write prescrubbed command help!");
+}
+
+pub fn log_scrubbed_output(command: &str, scrubbed_response: &str) {
+    fs::write(
+        format!("{}{}.txt", name_logdirs().4, &command),
+        scrubbed_response,
+    )
+    .expect("panic during fs::write scrubbed response!");
+}
+
 pub fn log_blessed_output(blessed: Vec<String>) {
     let mut blessed_list = String::new();
     for command in blessed {
@@ -64,3 +105,19 @@ pub fn log_blessed_output(blessed: Vec<String>) {
     fs::write(format!("{}blessed.txt", name_logdirs().2), blessed_list)
         .expect("panic during fs::write blessed!");
 }
+
+pub fn log_rule_report(rule_report: &str) {
+    fs::write(
+        format!("./logs/{}/rule_report.json", create_version_name()),
+        rule_report,
+    )
+    .expect("panic during fs::write rule report!");
+}
+
+pub fn log_defect_report(defect_report: &str, defect_report_markdown: &str) {
+    let log_parent = format!("./logs/{}/", create_version_name());
+    fs::write(format!("{}defects.json", log_parent), defect_report)
+        .expect("panic during fs::write defect report!");
+    fs::write(format!("{}defects.md", log_parent), defect_report_markdown)
+        .expect("panic during fs::write defect report!");
+}
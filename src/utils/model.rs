@@ -0,0 +1,359 @@
+//! The interpretations quizface records under `output/<version>/`, read back
+//! as typed shapes for the exporters, together with the per-command
+//! metadata recorded next to them when there is any.
+use serde_json::{map::Map, Value};
+use std::path::Path;
+
+// Keys zcashd documents a map's dynamic keys with, e.g. `"transactionid"`
+// in the verbose `getrawmempool`.  An object whose only key is one of these
+// is a map.
+const DYNAMIC_KEYS: [&str; 3] = ["xxxx", "transactionid", "account"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    Decimal,
+    String,
+    Bool,
+    Hexadecimal,
+    /// Documented too poorly to interpret.
+    Insufficient,
+    Enum(Vec<String>),
+    Array(Box<Shape>),
+    Object(Vec<Field>),
+    /// An object keyed by values such as transaction ids, and the key
+    /// zcashd documents them with.
+    Map(String, Box<Shape>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub optional: bool,
+    pub shape: Shape,
+    pub description: Option<String>,
+    pub unit: Option<String>,
+}
+
+/// A positional parameter, as recorded by `structure_arguments`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Argument {
+    pub name: String,
+    /// zcashd's word for the type, e.g. `numeric` or `json array`.
+    pub kind: String,
+    pub required: bool,
+    pub default: Option<String>,
+    pub description: String,
+}
+
+/// An example invocation, as recorded by `structure_examples`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    pub line: String,
+    pub params: Option<Vec<Value>>,
+}
+
+pub struct Command {
+    pub name: String,
+    pub category: Option<String>,
+    pub summary: Option<String>,
+    pub deprecated: bool,
+    pub experimental: bool,
+    pub arguments: Vec<Argument>,
+    pub examples: Vec<Example>,
+    /// One shape per result variant, in help order.
+    pub results: Vec<Shape>,
+    /// What each result variant is documented for, e.g. `verbosity = 1`,
+    /// if that was recorded.
+    pub result_conditions: Vec<Option<String>>,
+    /// Everything recorded by `record_metadata`, or `Null` if it wasn't.
+    pub metadata: Value,
+}
+
+impl Command {
+    /// Panics, naming the command, if `interpretation` isn't one that
+    /// `produce_interpretation` records.
+    pub fn from_json(
+        name: &str,
+        interpretation: &Value,
+        metadata: Value,
+    ) -> Command {
+        let variants = interpretation.as_array().unwrap_or_else(|| {
+            panic!("Interpretation of '{}' is not an array!", name)
+        });
+        let results = variants
+            .iter()
+            .enumerate()
+            .map(|(index, variant)| {
+                let docs = &metadata["fields"][index];
+                Shape::from_json(variant, "", docs)
+            })
+            .collect();
+        let flag = |flag: &str| metadata["capabilities"][flag] == true;
+        let arguments = metadata["arguments"]
+            .as_array()
+            .map(|arguments| {
+                arguments.iter().map(Argument::from_json).collect()
+            })
+            .unwrap_or_default();
+        let examples = metadata["examples"]
+            .as_array()
+            .map(|examples| {
+                examples
+                    .iter()
+                    .map(|example| Example {
+                        line: example["line"]
+                            .as_str()
+                            .unwrap_or("")
+                            .to_string(),
+                        params: example["params"].as_array().cloned(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let result_conditions = metadata["result_conditions"]
+            .as_array()
+            .map(|conditions| {
+                conditions
+                    .iter()
+                    .map(|condition| condition.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Command {
+            name: name.to_string(),
+            category: metadata["category"].as_str().map(String::from),
+            summary: metadata["description"]["summary"]
+                .as_str()
+                .filter(|summary| !summary.is_empty())
+                .map(String::from),
+            deprecated: flag("deprecated"),
+            experimental: flag("experimental"),
+            arguments,
+            examples,
+            results,
+            result_conditions,
+            metadata,
+        }
+    }
+}
+
+impl Argument {
+    /// The shape of the values the argument takes, as far as its type word
+    /// tells.
+    pub fn shape(&self) -> Shape {
+        match self.kind.as_str() {
+            "numeric" => Shape::Decimal,
+            "string" => Shape::String,
+            "boolean" => Shape::Bool,
+            "hexadecimal" => Shape::Hexadecimal,
+            "array" | "json array" => {
+                Shape::Array(Box::new(Shape::Insufficient))
+            }
+            _ => Shape::Insufficient,
+        }
+    }
+
+    fn from_json(argument: &Value) -> Argument {
+        let text = |key: &str| argument[key].as_str().unwrap_or("").to_string();
+        Argument {
+            name: text("name"),
+            kind: text("type"),
+            required: argument["required"] != false,
+            default: argument["default"].as_str().map(String::from),
+            description: text("description"),
+        }
+    }
+}
+
+impl Shape {
+    // `docs` are the `fields` recorded for the variant, keyed by the JSON
+    // pointer of each field; `pointer` is that of `interpretation`.
+    fn from_json(interpretation: &Value, pointer: &str, docs: &Value) -> Shape {
+        match interpretation {
+            Value::String(label) => Shape::from_label(label),
+            Value::Array(items) => {
+                Shape::Array(Box::new(match items.first() {
+                    Some(item) => {
+                        Shape::from_json(item, &format!("{}/0", pointer), docs)
+                    }
+                    None => Shape::Insufficient,
+                }))
+            }
+            Value::Object(members) => {
+                if members.len() == 1 {
+                    let (key, value) = members.iter().next().unwrap();
+                    if DYNAMIC_KEYS.contains(&key.as_str()) {
+                        return Shape::Map(
+                            key.clone(),
+                            Box::new(Shape::from_json(
+                                value,
+                                &format!("{}/{}", pointer, escape(key)),
+                                docs,
+                            )),
+                        );
+                    }
+                }
+                Shape::Object(fields(members, pointer, docs))
+            }
+            other => panic!("Unexpected interpretation: {}", other),
+        }
+    }
+
+    fn from_label(label: &str) -> Shape {
+        match label {
+            "Decimal" => Shape::Decimal,
+            "String" => Shape::String,
+            "bool" => Shape::Bool,
+            "hexadecimal" => Shape::Hexadecimal,
+            "INSUFFICIENT" => Shape::Insufficient,
+            enumeration if enumeration.to_lowercase().starts_with("enum") => {
+                Shape::Enum(
+                    enumeration
+                        .split_once(':')
+                        .map_or("", |(_, values)| values)
+                        .split(',')
+                        .map(|value| value.trim().trim_matches('"').to_string())
+                        .filter(|value| !value.is_empty())
+                        .collect(),
+                )
+            }
+            label => panic!("Label '{}' is invalid", label),
+        }
+    }
+}
+
+fn fields(
+    members: &Map<String, Value>,
+    pointer: &str,
+    docs: &Value,
+) -> Vec<Field> {
+    members
+        .iter()
+        .map(|(key, value)| {
+            let member_pointer = format!("{}/{}", pointer, escape(key));
+            let doc = |about: &str| {
+                docs[&member_pointer][about].as_str().map(String::from)
+            };
+            let (name, optional) = match key
+                .strip_prefix("Option<")
+                .and_then(|k| k.strip_suffix('>'))
+            {
+                Some(name) => (name.to_string(), true),
+                None => (key.clone(), false),
+            };
+            Field {
+                name,
+                optional,
+                shape: Shape::from_json(value, &member_pointer, docs),
+                description: doc("description"),
+                unit: doc("unit"),
+            }
+        })
+        .collect()
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Every command interpreted in `output_dir`, e.g. `output/v4.3.0_0.2.0`,
+/// sorted by name.
+pub fn load_commands(output_dir: &Path) -> Vec<Command> {
+    let mut locations = std::fs::read_dir(output_dir)
+        .unwrap_or_else(|e| panic!("Couldn't read {:?}: {}", output_dir, e))
+        .map(|entry| entry.expect("Couldn't read output entry!").path())
+        .filter(|path| {
+            path.is_file() && path.extension().is_some_and(|ext| ext == "json")
+        })
+        .filter(|path| path.file_stem().is_some_and(|stem| stem != "index"))
+        .collect::<Vec<_>>();
+    locations.sort();
+    locations
+        .iter()
+        .map(|location| {
+            let name = location
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .expect("Invalid command file name!");
+            let metadata_location =
+                output_dir.join("metadata").join(format!("{}.json", name));
+            let metadata = if metadata_location.is_file() {
+                read_json(&metadata_location)
+            } else {
+                Value::Null
+            };
+            Command::from_json(name, &read_json(location), metadata)
+        })
+        .collect()
+}
+
+fn read_json(location: &Path) -> Value {
+    let reader = std::io::BufReader::new(
+        std::fs::File::open(location).expect("Couldn't open output file!"),
+    );
+    serde_json::from_reader(reader)
+        .unwrap_or_else(|e| panic!("Invalid {:?}: {}", location, e))
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn command_from_json_getrawmempool() {
+        let command = Command::from_json(
+            "getrawmempool",
+            &json!([["String"], {"transactionid": {"size": "Decimal"}}]),
+            json!({
+                "category": "Blockchain",
+                "fields": [
+                    {},
+                    {"/transactionid/size": {
+                        "description": "transaction size in bytes",
+                        "unit": "bytes"
+                    }}
+                ]
+            }),
+        );
+        assert_eq!(command.category.as_deref(), Some("Blockchain"));
+        assert_eq!(command.results[0], Shape::Array(Box::new(Shape::String)));
+        assert_eq!(
+            command.results[1],
+            Shape::Map(
+                "transactionid".to_string(),
+                Box::new(Shape::Object(vec![Field {
+                    name: "size".to_string(),
+                    optional: false,
+                    shape: Shape::Decimal,
+                    description: Some("transaction size in bytes".to_string()),
+                    unit: Some("bytes".to_string()),
+                }]))
+            )
+        );
+    }
+
+    #[test]
+    fn shape_from_json_optional_and_enum() {
+        let shape = Shape::from_json(
+            &json!({"Option<proxy>": "String"}),
+            "",
+            &json!({"/Option<proxy>": {"description": "the proxy"}}),
+        );
+        match shape {
+            Shape::Object(fields) => {
+                assert_eq!(fields[0].name, "proxy");
+                assert!(fields[0].optional);
+                assert_eq!(fields[0].description.as_deref(), Some("the proxy"));
+            }
+            other => panic!("Not an object: {:?}", other),
+        }
+        assert_eq!(
+            Shape::from_label("ENUM: duplicate, inconclusive"),
+            Shape::Enum(vec![
+                "duplicate".to_string(),
+                "inconclusive".to_string()
+            ])
+        );
+    }
+}
@@ -0,0 +1,288 @@
+//! Help-text corrections too large to read as a `rules` entry are kept as
+//! unified diffs against the raw help logged under `help_output/raw/`, at
+//! `PATCHES_DIR/<zcashd version>/<command>.patch`.  A command is patched
+//! with the patch written for the newest zcashd release not newer than the
+//! one being interpreted.
+//!
+//! A patched command that is left without scrub rules of its own is
+//! scrubbed with the `_` rules, like any other; the patches of v4.3.0 drop
+//! the ellipses those rules look for, so they change nothing.
+//!
+//! Like `patch`, a hunk is looked for first where its header says, then at
+//! the nearest offset, and finally with up to `MAX_FUZZ` of its leading and
+//! trailing context lines ignored.  Every hunk yields a `RuleOutcome`, so a
+//! patch that no longer applies is reported as stale.
+use crate::utils::rules::{parse_version, Change, Phase, RuleOutcome};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+pub const PATCHES_DIR: &str = "./patches/";
+const MAX_FUZZ: usize = 2;
+
+#[derive(Debug, PartialEq)]
+enum Line {
+    Context(String),
+    Removed(String),
+    Added(String),
+}
+
+#[derive(Debug)]
+struct Hunk {
+    old_start: usize,
+    lines: Vec<Line>,
+}
+
+impl Hunk {
+    fn leading_context(&self, fuzz: usize) -> usize {
+        self.lines
+            .iter()
+            .take_while(|l| matches!(l, Line::Context(_)))
+            .count()
+            .min(fuzz)
+    }
+
+    // The lines the hunk expects and the lines it leaves, ignoring `fuzz`
+    // context lines at either end.
+    fn sides(&self, fuzz: usize) -> (Vec<&str>, Vec<&str>) {
+        let leading = self.leading_context(fuzz);
+        let trailing = self
+            .lines
+            .iter()
+            .rev()
+            .take_while(|l| matches!(l, Line::Context(_)))
+            .count()
+            .min(fuzz);
+        let mut old = vec![];
+        let mut new = vec![];
+        for line in &self.lines[leading..self.lines.len() - trailing] {
+            match line {
+                Line::Context(text) => {
+                    old.push(text.as_str());
+                    new.push(text.as_str());
+                }
+                Line::Removed(text) => old.push(text.as_str()),
+                Line::Added(text) => new.push(text.as_str()),
+            }
+        }
+        (old, new)
+    }
+}
+
+fn parse_patch(patch: &str) -> Vec<Hunk> {
+    let header = Regex::new(r"^@@ -(\d+)(?:,(\d+))? \+\d+(?:,(\d+))? @@")
+        .expect("Invalid regex!");
+    let mut hunks = vec![];
+    let mut lines = patch.lines();
+    while let Some(line) = lines.next() {
+        let caps = match header.captures(line) {
+            Some(caps) => caps,
+            None => continue,
+        };
+        let count = |i| {
+            caps.get(i).map_or(1, |m| {
+                m.as_str().parse().expect("Invalid hunk header!")
+            })
+        };
+        let (mut old_count, mut new_count) = (count(2), count(3));
+        let mut hunk = Hunk {
+            old_start: caps[1].parse().expect("Invalid hunk header!"),
+            lines: vec![],
+        };
+        while old_count > 0 || new_count > 0 {
+            let line = lines.next().expect("Truncated hunk!");
+            // Editors strip the space off blank context lines.
+            let (marker, text) = match line.chars().next() {
+                Some(marker) => (marker, line[1..].to_string()),
+                None => (' ', String::new()),
+            };
+            match marker {
+                ' ' => {
+                    old_count -= 1;
+                    new_count -= 1;
+                    hunk.lines.push(Line::Context(text));
+                }
+                '-' => {
+                    old_count -= 1;
+                    hunk.lines.push(Line::Removed(text));
+                }
+                '+' => {
+                    new_count -= 1;
+                    hunk.lines.push(Line::Added(text));
+                }
+                '\\' => (),
+                _ => panic!("Invalid hunk line: {}", line),
+            }
+        }
+        hunks.push(hunk);
+    }
+    hunks
+}
+
+// Where `old` occurs in `lines`, preferring the occurrence nearest `expected`.
+fn locate(lines: &[String], old: &[&str], expected: usize) -> Option<usize> {
+    if old.len() > lines.len() {
+        return None;
+    }
+    let last = lines.len() - old.len();
+    let matches_at = |start: usize| {
+        lines[start..start + old.len()]
+            .iter()
+            .zip(old)
+            .all(|(line, old_line)| line == old_line)
+    };
+    let expected = expected.min(last);
+    (0..=last)
+        .flat_map(|offset| {
+            vec![expected.checked_sub(offset), Some(expected + offset)]
+        })
+        .flatten()
+        .filter(|&start| start <= last)
+        .find(|&start| matches_at(start))
+}
+
+/// The patch for `command` written against the newest zcashd release not
+/// newer than `zcashd_version`, if any.
+fn select_patch(command: &str, zcashd_version: &str) -> Option<PathBuf> {
+    let version = parse_version(zcashd_version);
+    let mut candidates = std::fs::read_dir(Path::new(PATCHES_DIR))
+        .ok()?
+        .map(|entry| entry.expect("Couldn't read patches entry!").path())
+        .filter(|dir| {
+            dir.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| parse_version(name) <= version)
+        })
+        .map(|dir| dir.join(format!("{}.patch", command)))
+        .filter(|location| location.is_file())
+        .collect::<Vec<PathBuf>>();
+    candidates.sort_by_key(|location| {
+        parse_version(
+            location
+                .parent()
+                .and_then(|dir| dir.file_name())
+                .and_then(|name| name.to_str())
+                .unwrap_or(""),
+        )
+    });
+    candidates.pop()
+}
+
+/// Applies the hunks of `patch`, identified as `patch_id`, to
+/// `raw_command_help`.
+pub fn apply_patch(
+    patch_id: &str,
+    patch: &str,
+    command: &str,
+    raw_command_help: &str,
+) -> (String, Vec<RuleOutcome>) {
+    let mut lines = raw_command_help
+        .split('\n')
+        .map(String::from)
+        .collect::<Vec<String>>();
+    let mut outcomes = vec![];
+    // How far the text has moved from the line numbers in hunk headers.
+    let mut delta: isize = 0;
+    for (number, hunk) in parse_patch(patch).iter().enumerate() {
+        let mut change = None;
+        for fuzz in 0..=MAX_FUZZ {
+            let (old, new) = hunk.sides(fuzz);
+            let leading = hunk.leading_context(fuzz);
+            let expected = ((hunk.old_start.max(1) - 1 + leading) as isize
+                + delta)
+                .max(0) as usize;
+            if let Some(start) = locate(&lines, &old, expected) {
+                if fuzz > 0 || start != expected {
+                    eprintln!(
+                        "{}: hunk #{} applied at line {} with fuzz {}",
+                        patch_id,
+                        number + 1,
+                        start + 1,
+                        fuzz
+                    );
+                }
+                lines.splice(
+                    start..start + old.len(),
+                    new.iter().map(|l| l.to_string()),
+                );
+                delta += start as isize - expected as isize
+                    + new.len() as isize
+                    - old.len() as isize;
+                change = Some(Change::new(&old.join("\n"), &new.join("\n")));
+                break;
+            }
+        }
+        outcomes.push(RuleOutcome {
+            rule_id: format!("{}#{}", patch_id, number + 1),
+            command: command.to_string(),
+            phase: Phase::Prescrub,
+            default: false,
+            change,
+        });
+    }
+    (lines.join("\n"), outcomes)
+}
+
+/// Applies the patch selected for `command` and `zcashd_version`, if there
+/// is one, to its raw help.
+pub fn apply_patches(
+    command: &str,
+    raw_command_help: &str,
+    zcashd_version: &str,
+) -> (String, Vec<RuleOutcome>) {
+    match select_patch(command, zcashd_version) {
+        Some(location) => {
+            let patch = std::fs::read_to_string(&location)
+                .expect("Couldn't read patch!");
+            apply_patch(
+                &location.display().to_string(),
+                &patch,
+                command,
+                raw_command_help,
+            )
+        }
+        None => (raw_command_help.to_string(), vec![]),
+    }
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use crate::utils::test;
+
+    #[test]
+    fn apply_patch_at_offset() {
+        let raw = format!("moved\ndown\n{}", test::PATCHABLE_HELP);
+        let (patched, outcomes) =
+            apply_patch("test.patch", test::HELP_PATCH, "test", &raw);
+        assert_eq!(patched, format!("moved\ndown\n{}", test::PATCHED_HELP));
+        assert_eq!(outcomes[0].rule_id, "test.patch#1");
+        assert!(outcomes[0].matched());
+    }
+
+    #[test]
+    fn apply_patch_with_fuzz() {
+        let raw = test::PATCHABLE_HELP.replace("1. verbose", "1. verbosity");
+        let (patched, outcomes) =
+            apply_patch("test.patch", test::HELP_PATCH, "test", &raw);
+        assert_eq!(
+            patched,
+            test::PATCHED_HELP.replace("1. verbose", "1. verbosity")
+        );
+        assert_eq!(
+            outcomes[0].change,
+            Some(Change::new(
+                "\nResult: (for verbose = false):\n\"data\"  (string) the data",
+                "\nResult:\n\"data\"  (string) the data"
+            ))
+        );
+    }
+
+    #[test]
+    fn apply_patch_no_longer_applies() {
+        let raw = test::PATCHABLE_HELP.replace("for verbose", "if verbose");
+        let (patched, outcomes) =
+            apply_patch("test.patch", test::HELP_PATCH, "test", &raw);
+        assert_eq!(patched, raw);
+        assert!(outcomes[0].is_stale());
+    }
+}
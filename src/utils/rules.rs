@@ -0,0 +1,552 @@
+//! Help-text corrections are data, not code: they live in the JSON files
+//! under `RULES_DIR` and are read every time they are applied, so fixing a
+//! badly formatted help message does not require recompiling quizface.
+//!
+//! Each file holds an array of rules such as:
+//!
+//! ```json
+//! {
+//!   "id": "getblock/result-header",
+//!   "commands": ["getblock"],
+//!   "phase": "prescrub",
+//!   "until": "v4.4.0",
+//!   "pattern": "Result \\(for verbosity = [012]\\):",
+//!   "replace": "Result:"
+//! }
+//! ```
+//!
+//! `find` is replaced literally, `pattern` is a regex whose replacement may
+//! refer to captures as `$1`.  `find`, `pattern` and `replace` may also be
+//! given as an array of lines.  Files are read in name order and a command's
+//! rules are applied in the order they appear.  Rules for the command `_`
+//! only apply to commands that have no rule of their own in that phase.
+//!
+//! A rule may be restricted to the zcashd releases whose help text it was
+//! written against with `"since"` (inclusive) and `"until"` (exclusive),
+//! e.g. `"since": "v4.3.0", "until": "v4.4.0"`.  The rules here run
+//! `until` the first release they haven't been checked against, whose help
+//! is left as it is rather than corrupted; checking a rule against a newer
+//! release means raising its bound.
+//!
+//! Applying a rule whose text no longer occurs is a silent no-op, so every
+//! application yields a `RuleOutcome` recording whether the rule matched,
+//! and if so a `Change` showing what it did.
+use regex::Regex;
+use serde_json::{json, Value};
+use std::path::Path;
+
+pub const RULES_DIR: &str = "./rules/";
+const DEFAULT_COMMAND: &str = "_";
+// Longest `Change` snippet, in chars, before it is elided.
+const SNIPPET_LEN: usize = 240;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Phase {
+    Prescrub,
+    Scrub,
+}
+
+impl Phase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Prescrub => "prescrub",
+            Phase::Scrub => "scrub",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Phase> {
+        match name {
+            "prescrub" => Some(Phase::Prescrub),
+            "scrub" => Some(Phase::Scrub),
+            _ => None,
+        }
+    }
+}
+
+enum Find {
+    Literal(String),
+    Pattern(Regex),
+}
+
+pub struct Rule {
+    pub id: String,
+    pub commands: Vec<String>,
+    pub phase: Phase,
+    since: Option<Vec<u64>>,
+    until: Option<Vec<u64>>,
+    find: Find,
+    replace: String,
+}
+
+impl Rule {
+    /// Panics, naming the offending rule, if `rule` is malformed.
+    pub fn from_json(rule: &Value) -> Rule {
+        let id = rule["id"]
+            .as_str()
+            .unwrap_or_else(|| panic!("Rule without an id: {}", rule))
+            .to_string();
+        let commands = rule["commands"]
+            .as_array()
+            .unwrap_or_else(|| panic!("Rule '{}' has no commands!", id))
+            .iter()
+            .map(|c| c.as_str().expect("Command is not a string!").to_string())
+            .collect();
+        let phase = rule["phase"]
+            .as_str()
+            .and_then(Phase::from_name)
+            .unwrap_or_else(|| panic!("Rule '{}' has an invalid phase!", id));
+        let version_bound = |key: &str| {
+            rule[key].as_str().map(parse_version).or_else(|| {
+                if rule[key].is_null() {
+                    None
+                } else {
+                    panic!("Rule '{}' has an invalid {}!", id, key)
+                }
+            })
+        };
+        let since = version_bound("since");
+        let until = version_bound("until");
+        let find = match (text(&rule["find"]), text(&rule["pattern"])) {
+            (Some(literal), None) => Find::Literal(literal),
+            (None, Some(pattern)) => {
+                Find::Pattern(Regex::new(&pattern).unwrap_or_else(|e| {
+                    panic!("Rule '{}' has an invalid pattern: {}", id, e)
+                }))
+            }
+            _ => panic!("Rule '{}' needs exactly one of find or pattern!", id),
+        };
+        let replace = text(&rule["replace"])
+            .unwrap_or_else(|| panic!("Rule '{}' has no replace!", id));
+        Rule {
+            id,
+            commands,
+            phase,
+            since,
+            until,
+            find,
+            replace,
+        }
+    }
+
+    pub fn applies_to(&self, command: &str) -> bool {
+        self.commands.iter().any(|c| c == command)
+    }
+
+    pub fn applies_to_version(&self, zcashd_version: &str) -> bool {
+        let version = parse_version(zcashd_version);
+        self.since.as_ref().is_none_or(|since| &version >= since)
+            && self.until.as_ref().is_none_or(|until| &version < until)
+    }
+
+    /// The corrected text, and the first correction made if the rule found
+    /// anything to correct.
+    pub fn apply(&self, help_text: &str) -> (String, Option<Change>) {
+        match &self.find {
+            Find::Literal(literal) => (
+                help_text.replace(literal, &self.replace),
+                if help_text.contains(literal.as_str()) {
+                    Some(Change::new(literal, &self.replace))
+                } else {
+                    None
+                },
+            ),
+            Find::Pattern(pattern) => (
+                pattern
+                    .replace_all(help_text, self.replace.as_str())
+                    .to_string(),
+                pattern.captures(help_text).map(|caps| {
+                    let mut after = String::new();
+                    caps.expand(&self.replace, &mut after);
+                    Change::new(&caps[0], &after)
+                }),
+            ),
+        }
+    }
+}
+
+/// A snippet of help text before and after a rule corrected it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub before: String,
+    pub after: String,
+}
+
+impl Change {
+    pub(crate) fn new(before: &str, after: &str) -> Change {
+        Change {
+            before: snippet(before),
+            after: snippet(after),
+        }
+    }
+}
+
+fn snippet(text: &str) -> String {
+    match text.char_indices().nth(SNIPPET_LEN) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RuleOutcome {
+    pub rule_id: String,
+    pub command: String,
+    pub phase: Phase,
+    /// Applied through the `_` fallback, which is not expected to match.
+    pub default: bool,
+    pub change: Option<Change>,
+}
+
+impl RuleOutcome {
+    pub fn matched(&self) -> bool {
+        self.change.is_some()
+    }
+
+    /// A rule written for this command that no longer matches its help.
+    pub fn is_stale(&self) -> bool {
+        !self.matched() && !self.default
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut outcome = json!({
+            "rule_id": self.rule_id,
+            "command": self.command,
+            "phase": self.phase.name(),
+            "matched": self.matched(),
+        });
+        if let Some(change) = &self.change {
+            outcome["before"] = json!(change.before);
+            outcome["after"] = json!(change.after);
+        }
+        outcome
+    }
+}
+
+/// The corrections `outcomes` made to a command's help text, in the order
+/// they were made.
+pub fn provenance(outcomes: &[RuleOutcome]) -> Value {
+    outcomes
+        .iter()
+        .filter(|o| o.matched())
+        .map(|o| o.to_json())
+        .collect()
+}
+
+/// Reports stale outcomes on stderr, where they precede any panic the
+/// resulting malformed help text causes further on.
+pub fn warn_stale(outcomes: &[RuleOutcome]) {
+    for outcome in outcomes.iter().filter(|o| o.is_stale()) {
+        eprintln!(
+            "warning: {} rule '{}' did not match the help of '{}'",
+            outcome.phase.name(),
+            outcome.rule_id,
+            outcome.command
+        );
+    }
+}
+
+/// Every `RuleOutcome` of a run.
+#[derive(Default)]
+pub struct RuleReport {
+    outcomes: Vec<RuleOutcome>,
+}
+
+impl RuleReport {
+    pub fn record(&mut self, outcomes: Vec<RuleOutcome>) {
+        self.outcomes.extend(outcomes);
+    }
+
+    pub fn outcomes(&self) -> &[RuleOutcome] {
+        &self.outcomes
+    }
+
+    pub fn stale(&self) -> Vec<&RuleOutcome> {
+        self.outcomes.iter().filter(|o| o.is_stale()).collect()
+    }
+
+    pub fn to_json(&self) -> Value {
+        let own = self.outcomes.iter().filter(|o| !o.default);
+        json!({
+            "applied": own.clone().count(),
+            "matched": own.filter(|o| o.matched()).count(),
+            "stale": self
+                .stale()
+                .iter()
+                .map(|o| o.to_json())
+                .collect::<Vec<Value>>(),
+        })
+    }
+}
+
+/// `v4.3.0-rc1` -> `[4, 3, 0]`; pre-release and build suffixes are ignored.
+pub fn parse_version(version: &str) -> Vec<u64> {
+    version
+        .trim()
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()
+        .unwrap_or("")
+        .split('.')
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+// A string, or an array of lines joined with `\n`.
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Array(lines) => Some(
+            lines
+                .iter()
+                .map(|l| l.as_str().expect("Line is not a string!"))
+                .collect::<Vec<&str>>()
+                .join("\n"),
+        ),
+        _ => None,
+    }
+}
+
+pub fn load_rules(phase: Phase) -> Vec<Rule> {
+    let mut locations = std::fs::read_dir(Path::new(RULES_DIR))
+        .expect("Couldn't read rules directory!")
+        .map(|entry| entry.expect("Couldn't read rules entry!").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect::<Vec<_>>();
+    locations.sort();
+    let mut rules = vec![];
+    for location in locations {
+        let reader = std::io::BufReader::new(
+            std::fs::File::open(&location).expect("Couldn't open rules!"),
+        );
+        let rule_values: Vec<Value> = serde_json::from_reader(reader)
+            .unwrap_or_else(|e| panic!("Invalid {:?}: {}", location, e));
+        rules.extend(
+            rule_values
+                .iter()
+                .map(Rule::from_json)
+                .filter(|rule| rule.phase == phase),
+        );
+    }
+    rules
+}
+
+/// The rules of `rules` that apply to `command` as documented by
+/// `zcashd_version`, in order.
+pub fn select_rules<'a>(
+    rules: &'a [Rule],
+    command: &str,
+    zcashd_version: &str,
+) -> Vec<&'a Rule> {
+    let in_range = rules
+        .iter()
+        .filter(|rule| rule.applies_to_version(zcashd_version))
+        .collect::<Vec<&Rule>>();
+    let own = in_range
+        .iter()
+        .filter(|rule| rule.applies_to(command))
+        .cloned()
+        .collect::<Vec<&Rule>>();
+    if own.is_empty() {
+        in_range
+            .into_iter()
+            .filter(|rule| rule.applies_to(DEFAULT_COMMAND))
+            .collect()
+    } else {
+        own
+    }
+}
+
+pub fn apply_rules(
+    phase: Phase,
+    command: &str,
+    help_text: &str,
+    zcashd_version: &str,
+) -> (String, Vec<RuleOutcome>) {
+    let rules = load_rules(phase);
+    let mut text = help_text.to_string();
+    let mut outcomes = vec![];
+    for rule in select_rules(&rules, command, zcashd_version) {
+        let (applied, change) = rule.apply(&text);
+        text = applied;
+        outcomes.push(RuleOutcome {
+            rule_id: rule.id.clone(),
+            command: command.to_string(),
+            phase,
+            default: !rule.applies_to(command),
+            change,
+        });
+    }
+    (text, outcomes)
+}
+
+#[cfg(test)]
+mod unit {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn rule_from_json_lines_and_pattern() {
+        let rule = Rule::from_json(&json!({
+            "id": "test/lines",
+            "commands": ["test"],
+            "phase": "scrub",
+            "pattern": "(\\w+)\\s+\\(bool\\)",
+            "replace": ["$1:", "(boolean)"]
+        }));
+        assert_eq!(
+            rule.apply("status (bool)"),
+            (
+                "status:\n(boolean)".to_string(),
+                Some(Change {
+                    before: "status (bool)".to_string(),
+                    after: "status:\n(boolean)".to_string(),
+                })
+            )
+        );
+        assert_eq!(rule.apply("status"), ("status".to_string(), None));
+        assert!(rule.applies_to("test"));
+        assert!(!rule.applies_to("_"));
+    }
+
+    #[test]
+    fn select_rules_falls_back_to_default() {
+        let rules = vec![
+            Rule::from_json(&json!({
+                "id": "_/drop", "commands": ["_"], "phase": "scrub",
+                "find": ",...", "replace": ""
+            })),
+            Rule::from_json(&json!({
+                "id": "own/number", "commands": ["own"], "phase": "scrub",
+                "find": "number", "replace": "numeric"
+            })),
+        ];
+        let ids = |command| {
+            select_rules(&rules, command, "v4.3.0")
+                .iter()
+                .map(|r| r.id.clone())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(ids("own"), vec!["own/number"]);
+        assert_eq!(ids("other"), vec!["_/drop"]);
+    }
+
+    #[test]
+    fn select_rules_by_zcashd_version() {
+        let rules = vec![
+            Rule::from_json(&json!({
+                "id": "own/old", "commands": ["own"], "phase": "scrub",
+                "until": "v4.4.0", "find": "number", "replace": "numeric"
+            })),
+            Rule::from_json(&json!({
+                "id": "own/new", "commands": ["own"], "phase": "scrub",
+                "since": "v4.4.0", "find": "num", "replace": "numeric"
+            })),
+        ];
+        let ids = |zcashd_version| {
+            select_rules(&rules, "own", zcashd_version)
+                .iter()
+                .map(|r| r.id.clone())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(ids("v4.3.0"), vec!["own/old"]);
+        assert_eq!(ids("v4.4.0-rc1"), vec!["own/new"]);
+        assert_eq!(ids("v5.0.0"), vec!["own/new"]);
+    }
+
+    #[test]
+    fn load_rules_skips_rules_until_a_later_version() {
+        let rules = load_rules(Phase::Prescrub);
+        let ids = |zcashd_version| {
+            select_rules(&rules, "zcrawkeygen", zcashd_version)
+                .iter()
+                .map(|r| r.id.clone())
+                .collect::<Vec<String>>()
+        };
+        assert!(ids("v4.2.0").contains(&"zcrawkeygen/zcaddr".to_string()));
+        assert!(ids("v4.3.0").contains(&"zcrawkeygen/zcaddr".to_string()));
+        assert!(ids("v4.4.0").is_empty());
+    }
+
+    #[test]
+    fn apply_rules_older_and_newer_versions() {
+        let help = "\"txid\": \"id\", (string) The id.\n\
+                    {}and more of it\n";
+        let help = help.replace("{}", &" ".repeat(41));
+        let applied = |zcashd_version| {
+            apply_rules(Phase::Scrub, "listtransactions", &help, zcashd_version)
+        };
+        for older in ["v4.2.0", "v4.3.0"] {
+            let (text, outcomes) = applied(older);
+            assert_eq!(text, "\"txid\": \"id\", (string) The id.\n");
+            assert!(outcomes.iter().all(|o| o.matched()));
+        }
+        let (text, outcomes) = applied("v4.4.0");
+        assert_eq!(text, help);
+        assert!(outcomes.is_empty());
+    }
+
+    #[test]
+    fn rule_report_ignores_unmatched_defaults() {
+        let outcome = |rule_id: &str, default, matched| RuleOutcome {
+            rule_id: rule_id.to_string(),
+            command: "own".to_string(),
+            phase: Phase::Scrub,
+            default,
+            change: if matched {
+                Some(Change::new("number", "numeric"))
+            } else {
+                None
+            },
+        };
+        let mut report = RuleReport::default();
+        report.record(vec![
+            outcome("own/matched", false, true),
+            outcome("own/stale", false, false),
+            outcome("_/drop", true, false),
+        ]);
+        let stale = report.stale();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].rule_id, "own/stale");
+        assert_eq!(report.to_json()["applied"], 2);
+        assert_eq!(report.to_json()["matched"], 1);
+    }
+
+    #[test]
+    fn provenance_elides_long_snippets() {
+        let rule = Rule::from_json(&json!({
+            "id": "own/header", "commands": ["own"], "phase": "prescrub",
+            "find": "x".repeat(SNIPPET_LEN + 1), "replace": "Result:"
+        }));
+        let (_, change) = rule.apply(&"x".repeat(SNIPPET_LEN + 1));
+        let outcomes = vec![RuleOutcome {
+            rule_id: rule.id.clone(),
+            command: "own".to_string(),
+            phase: Phase::Prescrub,
+            default: false,
+            change,
+        }];
+        let provenance = provenance(&outcomes);
+        assert_eq!(provenance.as_array().unwrap().len(), 1);
+        assert_eq!(
+            provenance[0]["before"],
+            format!("{}...", "x".repeat(SNIPPET_LEN))
+        );
+        assert_eq!(provenance[0]["after"], "Result:");
+    }
+
+    #[test]
+    fn parse_version_zcash_cli() {
+        assert_eq!(parse_version("v4.3.0"), vec![4, 3, 0]);
+        assert_eq!(parse_version("v4.4.1-rc1"), vec![4, 4, 1]);
+        assert!(parse_version("v4.3") < parse_version("v4.3.0"));
+    }
+
+    #[test]
+    #[should_panic(expected = "needs exactly one of find or pattern")]
+    fn rule_from_json_both_find_and_pattern() {
+        Rule::from_json(&json!({
+            "id": "test/both", "commands": ["test"], "phase": "scrub",
+            "find": "a", "pattern": "b", "replace": ""
+        }));
+    }
+}
@@ -951,3 +951,246 @@ pub const BINDING_ENFORCE: [(&str, &str); 4] = [
     ("required", "Decimal"),
     ("window", "Decimal"),
 ];
+
+pub const DEPRECATED_EXPERIMENTAL_DESCRIPTION: &str = r#"getaccount "zcashaddress"
+
+EXPERIMENTAL FEATURE
+
+DEPRECATED. Returns the account associated with the given address.
+
+Requires wallet passphrase to be set with walletpassphrase call.
+"#;
+
+pub const DUMPPRIVKEY_DESCRIPTION: &str = r#"dumpprivkey "t-addr"
+
+Reveals the private key corresponding to 't-addr'.
+Then the importprivkey can be used with this output
+"#;
+
+pub const MASTERHELP_FRAGMENT: &str = r#"== Blockchain ==
+getbestblockhash
+getblock "hash|height" ( verbosity )
+
+== Control ==
+getinfo
+help ( "command" )
+
+== Wallet ==
+dumpprivkey "t-addr"
+z_exportkey "zaddr"
+"#;
+
+/// The zcashd release the help text fixtures in this module come from.
+pub const ZCASHD_VERSION: &str = "v4.3.0";
+
+pub const PATCHABLE_HELP: &str = r#"test ( verbose )
+
+Arguments:
+1. verbose  (boolean, optional) true for a json object
+
+Result: (for verbose = false):
+"data"  (string) the data
+
+Examples:
+> zcash-cli test
+"#;
+
+pub const HELP_PATCH: &str = r#"--- a/help_output/raw/test.txt
++++ b/help_output/raw/test.txt
+@@ -3,7 +3,7 @@
+ Arguments:
+ 1. verbose  (boolean, optional) true for a json object
+
+-Result: (for verbose = false):
++Result:
+ "data"  (string) the data
+
+ Examples:
+"#;
+
+pub const PATCHED_HELP: &str = r#"test ( verbose )
+
+Arguments:
+1. verbose  (boolean, optional) true for a json object
+
+Result:
+"data"  (string) the data
+
+Examples:
+> zcash-cli test
+"#;
+
+pub const LINT_BADHELP: &str = r#"badhelp
+
+Result:
+{
+  "fine": n,        (numeric) fine
+  "word": n,        (number) not numeric
+  "colon" n,        (numeric) no colon
+  "quote": n,       isn’t typed
+  nokey: n,         (numeric) no quoted key
+  "list": [         (array) never closed
+}
+]
+
+Examples:
+> zcash-cli badhelp
+"#;
+
+pub const LINT_TWO_RESULTS: &str = r#"getblock "hash" ( verbosity )
+
+Result (for verbosity = 0):
+"data"   (string) hex
+
+Result (for verbosity = 1):
+{
+  "hash" : "hash",   (string) the block hash
+}
+
+Examples:
+> zcash-cli getblock "hash"
+"#;
+
+pub const Z_SENDMANY_ARGUMENTS: &str = r#"
+1. "fromaddress"         (string, required) The taddr or zaddr to send the funds from.
+2. "amounts"             (array, required) An array of json objects representing the amounts to send.
+    [{
+      "address":address  (string, required) The address is a taddr or zaddr
+      "amount":amount    (numeric, required) The numeric amount in ZEC is the value
+      "memo":memo        (string, optional) If the address is a zaddr, raw data represented in hexadecimal string format
+    }, ... ]
+3. minconf               (numeric, optional, default=1) Only use funds confirmed at least this many times.
+4. fee                   (numeric, optional, default=0.0001) The fee amount to attach to this
+                         transaction.
+
+"#;
+
+pub const Z_SENDMANY_EXAMPLES: &str = r#"Examples:
+> zcash-cli z_sendmany "t1M72Sfpbz1BPpXFHz9m3CdqATR44Jvaydd" '[{"address": "ztfaW34Gj9FrnGUEf833ywDVL62NWXBM81u6EQnM6VR45eYnXhwztecW1SjxA7JrmAXKJhxhj3vDNEpVCQoSvVoSpmbhtjf" ,"amount": 5.0}]'
+> curl --user myusername --data-binary '{"jsonrpc": "1.0", "id":"curltest", "method": "z_sendmany", "params": ["t1M72Sfpbz1BPpXFHz9m3CdqATR44Jvaydd", [{"address": "ztfaW34Gj9FrnGUEf833ywDVL62NWXBM81u6EQnM6VR45eYnXhwztecW1SjxA7JrmAXKJhxhj3vDNEpVCQoSvVoSpmbhtjf" ,"amount": 5.0}]] }' -H 'content-type: text/plain;' http://127.0.0.1:8232/
+"#;
+
+pub const HELP_GETRAWMEMPOOL: &str = r#"getrawmempool ( verbose )
+
+Returns all transaction ids in memory pool as a json array of string transaction ids.
+
+Arguments:
+1. verbose           (boolean, optional, default=false) true for a json object, false for array of transaction ids
+
+Result: (for verbose = false):
+[                     (json array of string)
+  "transactionid"     (string) The transaction id
+  ,...
+]
+
+Result: (for verbose = true):
+{                           (json object)
+  "transactionid" : {       (json object)
+    "size" : n,             (numeric) transaction size in bytes
+    "fee" : n,              (numeric) transaction fee in ZEC
+    "time" : n,             (numeric) local time transaction entered pool in seconds since 1 Jan 1970 GMT
+    "height" : n,           (numeric) block height when transaction entered pool
+    "startingpriority" : n, (numeric) priority when transaction entered pool
+    "currentpriority" : n,  (numeric) transaction priority now
+    "depends" : [           (array) unconfirmed transactions used as inputs for this transaction
+        "transactionid",    (string) parent transaction id
+       ... ]
+  }, ...
+}
+
+Examples:
+> zcash-cli getrawmempool true
+> curl --user myusername --data-binary '{"jsonrpc": "1.0", "id":"curltest", "method": "getrawmempool", "params": [true] }' -H 'content-type: text/plain;' http://127.0.0.1:8232/
+"#;
+
+pub const HELP_GETBLOCKHEADER: &str = r#"getblockheader "hash" ( verbose )
+
+If verbose is false, returns a string that is serialized, hex-encoded data for blockheader 'hash'.
+If verbose is true, returns an Object with information about blockheader <hash>.
+
+Arguments:
+1. "hash"          (string, required) The block hash
+2. verbose           (boolean, optional, default=true) true for a json object, false for the hex encoded data
+
+Result (for verbose = true):
+{
+  "hash" : "hash",     (string) the block hash (same as provided)
+  "confirmations" : n,   (numeric) The number of confirmations, or -1 if the block is not on the main chain
+  "height" : n,          (numeric) The block height or index
+  "version" : n,         (numeric) The block version
+  "merkleroot" : "xxxx", (string) The merkle root
+  "finalsaplingroot" : "xxxx", (string) The root of the Sapling commitment tree after applying this block
+  "time" : ttt,          (numeric) The block time in seconds since epoch (Jan 1 1970 GMT)
+  "nonce" : n,           (numeric) The nonce
+  "bits" : "1d00ffff", (string) The bits
+  "difficulty" : x.xxx,  (numeric) The difficulty
+  "previousblockhash" : "hash",  (string) The hash of the previous block
+  "nextblockhash" : "hash"       (string) The hash of the next block
+}
+
+Result (for verbose=false):
+"data"             (string) A string that is serialized, hex-encoded data for block 'hash'.
+
+Examples:
+> zcash-cli getblockheader "00000000c937983704a73af28acdec37b049d214adbda81d7e2a3dd146f6ed09"
+> curl --user myusername --data-binary '{"jsonrpc": "1.0", "id":"curltest", "method": "getblockheader", "params": ["00000000c937983704a73af28acdec37b049d214adbda81d7e2a3dd146f6ed09"] }' -H 'content-type: text/plain;' http://127.0.0.1:8232/
+"#;
+
+pub const HELP_LISTTRANSACTIONS: &str = r#"listtransactions ( "account" count from includeWatchonly)
+
+Returns up to 'count' most recent transactions skipping the first 'from' transactions for account 'account'.
+
+Arguments:
+1. "account"    (string, optional) DEPRECATED. The account name. Should be "*".
+2. count          (numeric, optional, default=10) The number of transactions to return
+3. from           (numeric, optional, default=0) The number of transactions to skip
+4. includeWatchonly (bool, optional, default=false) Include transactions to watchonly addresses (see 'importaddress')
+
+Result:
+[
+  {
+    "account":"accountname",       (string) DEPRECATED. The account name associated with the transaction.
+                                                It will be "" for the default account.
+    "address":"zcashaddress",    (string) The Zcash address of the transaction. Not present for
+                                                move transactions (category = move).
+    "category":"send|receive|move", (string) The transaction category. 'move' is a local (off blockchain)
+                                                transaction between accounts, and not associated with an address,
+                                                transaction id or block. 'send' and 'receive' transactions are
+                                                associated with an address, transaction id and block details
+    "status" : "mined|waiting|expiringsoon|expired",    (string) The transaction status, can be 'mined' for transactions on the block chain, 'waiting' for transactions waiting to be mined, 'expiringsoon' for transactions waiting to be mined within the next 3 blocks, 'expired' for transactions that have expired.
+    "amount": x.xxx,          (numeric) The amount in ZEC. This is negative for the 'send' category, and for the
+                                         'move' category for moves outbound. It is positive for the 'receive' category,
+                                         and for the 'move' category for inbound funds.
+    "amountZat": x.xxx,       (numeric) The amount in zatoshis. Negative and positive are the same as 'amount' field.
+    "vout" : n,               (numeric) the vout value
+    "fee": x.xxx,             (numeric) The amount of the fee in ZEC. This is negative and only available for the
+                                         'send' category of transactions.
+    "confirmations": n,       (numeric) The number of confirmations for the transaction. Available for 'send' and
+                                         'receive' category of transactions.
+    "blockhash": "hashvalue", (string) The block hash containing the transaction. Available for 'send' and 'receive'
+                                          category of transactions.
+    "blockindex": n,          (numeric) The block index containing the transaction. Available for 'send' and 'receive'
+                                          category of transactions.
+    "txid": "transactionid", (string) The transaction id. Available for 'send' and 'receive' category of transactions.
+    "time": xxx,              (numeric) The transaction time in seconds since epoch (midnight Jan 1 1970 GMT).
+    "timereceived": xxx,      (numeric) The time received in seconds since epoch (midnight Jan 1 1970 GMT). Available
+                                          for 'send' and 'receive' category of transactions.
+    "comment": "...",       (string) If a comment is associated with the transaction.
+    "otheraccount": "accountname",  (string) For the 'move' category of transactions, the account the funds came
+                                          from (for receiving funds, positive amounts), or went to (for sending funds,
+                                          negative amounts).
+    "size": n,                (numeric) Transaction size in bytes
+  }
+]
+
+Examples:
+
+List the most recent 10 transactions in the systems
+> zcash-cli listtransactions 
+
+List transactions 100 to 120
+> zcash-cli listtransactions "*" 20 100
+
+As a json rpc call
+> curl --user myusername --data-binary '{"jsonrpc": "1.0", "id":"curltest", "method": "listtransactions", "params": ["*", 20, 100] }' -H 'content-type: text/plain;' http://127.0.0.1:8232/
+"#;